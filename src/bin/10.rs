@@ -81,7 +81,7 @@ impl Map {
         let mut neighbors = Vec::new();
         for d in DIRECTIONS.iter() {
             let neighbor = point + d;
-            if let Some(_) = self.map.get(&neighbor) {
+            if self.map.contains_key(&neighbor) {
                 neighbors.push(neighbor);
             }
         }
@@ -103,9 +103,7 @@ impl Map {
             }
 
             for neighbor in self.neighbors(&point) {
-                if visited.contains(&neighbor) {
-                    continue;
-                } else if self.get(&neighbor) != Some(height + 1) {
+                if visited.contains(&neighbor) || self.get(&neighbor) != Some(height + 1) {
                     continue;
                 }
                 visited.insert(neighbor);
@@ -160,20 +158,3 @@ pub fn part_two(input: &str) -> Option<usize> {
             .sum(),
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(36));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(81));
-    }
-}
@@ -51,9 +51,9 @@ impl StoneConfiguration {
     }
 
     fn size(&self) -> usize {
-        &self.zero_count
-            + &self.even_digits.values().sum::<usize>()
-            + &self.odd_digits.values().sum::<usize>()
+        self.zero_count
+            + self.even_digits.values().sum::<usize>()
+            + self.odd_digits.values().sum::<usize>()
     }
 }
 
@@ -85,10 +85,8 @@ fn split_number(number: usize) -> (usize, usize) {
 pub fn part_one(input: &str) -> Option<usize> {
     let mut stones = StoneConfiguration::default();
 
-    for number in input.split_whitespace().map(|s| s.parse::<usize>()) {
-        if let Ok(number) = number {
-            stones.insert_number(number, 1);
-        }
+    for number in input.split_whitespace().flat_map(|s| s.parse::<usize>()) {
+        stones.insert_number(number, 1);
     }
 
     for _ in 0..25 {
@@ -101,10 +99,8 @@ pub fn part_one(input: &str) -> Option<usize> {
 pub fn part_two(input: &str) -> Option<usize> {
     let mut stones = StoneConfiguration::default();
 
-    for number in input.split_whitespace().map(|s| s.parse::<usize>()) {
-        if let Ok(number) = number {
-            stones.insert_number(number, 1);
-        }
+    for number in input.split_whitespace().flat_map(|s| s.parse::<usize>()) {
+        stones.insert_number(number, 1);
     }
 
     for _ in 0..75 {
@@ -113,21 +109,3 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     Some(stones.size())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(65601038650482)); //inferred from running the code; not provided in
-                                                  //the examples
-    }
-}
@@ -4,7 +4,7 @@ use std::collections::{HashSet, VecDeque};
 
 const DIR: [(i32, i32); 4] = [(1, 0), (0, 1), (0, -1), (-1, 0)];
 
-fn get_neighbors(pos: (i32, i32), map: &Vec<Vec<char>>, plant: char) -> Vec<(i32, i32)> {
+fn get_neighbors(pos: (i32, i32), map: &[Vec<char>], plant: char) -> Vec<(i32, i32)> {
     let mut neighbors = Vec::new();
 
     for dir in DIR {
@@ -23,7 +23,7 @@ fn get_neighbors(pos: (i32, i32), map: &Vec<Vec<char>>, plant: char) -> Vec<(i32
 fn bfs(
     pos: (i32, i32),
     visited: &mut HashSet<(i32, i32)>,
-    map: &Vec<Vec<char>>,
+    map: &[Vec<char>],
 ) -> (HashSet<(i32, i32)>, usize) {
     let mut queue = VecDeque::new();
     let plant = map[pos.0 as usize][pos.1 as usize];
@@ -120,20 +120,3 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     Some(price)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1930));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1206));
-    }
-}
@@ -13,8 +13,8 @@ struct Equations {
 
 impl Equations {
     fn new(a: (u64, u64), b: (u64, u64), prize: (u64, u64)) -> Self {
-        let x = [a.0 as u64, b.0 as u64, prize.0 as u64];
-        let y = [a.1 as u64, b.1 as u64, prize.1 as u64];
+        let x = [a.0, b.0, prize.0];
+        let y = [a.1, b.1, prize.1];
 
         Self { x, y }
     }
@@ -123,20 +123,3 @@ pub fn part_two(input: &str) -> Option<u64> {
             .sum(),
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(480));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(875318608908));
-    }
-}
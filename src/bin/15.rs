@@ -1,8 +1,12 @@
-advent_of_code::solution!(15);
+advent_of_code::solution!(15, inspect);
 
+use advent_of_code::template::{runner::is_part_selected, ANSI_RESET};
 use itertools::Itertools;
 use std::collections::{HashSet, VecDeque};
-use std::ops::Add;
+use std::io::{self, Write};
+use std::ops::{Add, Range};
+use std::time::Duration;
+use std::{env, thread};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Point {
@@ -86,66 +90,315 @@ impl Warehouse {
         }
     }
 
-    fn solve(&mut self) -> usize {
-        'outer: for m in self.moves.iter() {
-            let mut queue = VecDeque::from([self.robot]);
-            let mut seen = HashSet::new();
-            while let Some(robot) = queue.pop_front() {
-                if !seen.insert(robot) {
-                    continue;
-                }
+    /// The robot and the boxes that it pushes when it moves in direction `m`, or `None` if a wall
+    /// blocks the move.
+    fn pushed(&self, m: &Point) -> Option<HashSet<Point>> {
+        let mut queue = VecDeque::from([self.robot]);
+        let mut seen = HashSet::new();
+        while let Some(robot) = queue.pop_front() {
+            if !seen.insert(robot) {
+                continue;
+            }
 
-                let robot_2 = &robot + m;
-
-                match self.grid[robot_2.y as usize][robot_2.x as usize] {
-                    '#' => continue 'outer,
-                    'O' => queue.push_back(robot_2),
-                    '[' => {
-                        let box_extension = Point {
-                            x: robot_2.x + 1,
-                            y: robot_2.y,
-                        };
-                        queue.extend([robot_2, box_extension]);
-                    }
-                    ']' => {
-                        let box_extension = Point {
-                            x: robot_2.x - 1,
-                            y: robot_2.y,
-                        };
-                        queue.extend([robot_2, box_extension]);
-                    }
-                    _ => continue,
+            let robot_2 = &robot + m;
+
+            match self.grid[robot_2.y as usize][robot_2.x as usize] {
+                '#' => return None,
+                'O' => queue.push_back(robot_2),
+                '[' => {
+                    let box_extension = Point {
+                        x: robot_2.x + 1,
+                        y: robot_2.y,
+                    };
+                    queue.extend([robot_2, box_extension]);
+                }
+                ']' => {
+                    let box_extension = Point {
+                        x: robot_2.x - 1,
+                        y: robot_2.y,
+                    };
+                    queue.extend([robot_2, box_extension]);
                 }
+                _ => continue,
             }
+        }
 
-            let boxes = seen
-                .iter()
-                .sorted_by_key(|p| (self.robot.x.abs_diff(p.x), self.robot.y.abs_diff(p.y)))
-                .rev()
-                .collect::<Vec<&Point>>();
+        Some(seen)
+    }
 
-            for b in boxes.iter() {
-                let p = *b + m;
-                self.grid[p.y as usize][p.x as usize] = self.grid[b.y as usize][b.x as usize];
-                self.grid[b.y as usize][b.x as usize] = '.';
-            }
-            self.robot = &self.robot + m;
+    /// Moves the `pushed` cells one step in direction `m`, see [`Warehouse::pushed`].
+    fn push(&mut self, m: &Point, pushed: &HashSet<Point>) {
+        let boxes = pushed
+            .iter()
+            .sorted_by_key(|p| (self.robot.x.abs_diff(p.x), self.robot.y.abs_diff(p.y)))
+            .rev()
+            .collect::<Vec<&Point>>();
+
+        for b in boxes.iter() {
+            let p = *b + m;
+            self.grid[p.y as usize][p.x as usize] = self.grid[b.y as usize][b.x as usize];
+            self.grid[b.y as usize][b.x as usize] = '.';
+        }
+        self.robot = &self.robot + m;
+    }
+
+    /// Moves the robot one step, pushing any boxes in the way.
+    fn step(&mut self, m: &Point) {
+        if let Some(pushed) = self.pushed(m) {
+            self.push(m, &pushed);
         }
+    }
 
+    fn gps(&self) -> usize {
         (0..self.grid.len())
             .cartesian_product(0..self.grid[0].len())
             .filter(|p| matches!(self.grid[p.0][p.1], 'O' | '['))
             .map(|p| p.0 * 100 + p.1)
             .sum()
     }
+
+    fn solve(&mut self) -> usize {
+        for m in self.moves.clone() {
+            self.step(&m);
+        }
+
+        self.gps()
+    }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut warehouse = Warehouse::new(input);
-    Some(warehouse.solve())
+/// The changes of a single move of the robot.
+struct Step {
+    direction: char,
+    changes: Vec<(Point, char, char)>,
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+/// A recording of every move of the robot, to look at the warehouse after any step.
+/// Only the changed cells of each step are stored, so recording large inputs stays cheap.
+struct Replay {
+    initial: Vec<Vec<char>>,
+    steps: Vec<Step>,
+}
+
+impl Replay {
+    fn record(mut warehouse: Warehouse) -> Self {
+        let initial = warehouse.grid.clone();
+        let steps = warehouse
+            .moves
+            .clone()
+            .iter()
+            .map(|m| Step {
+                direction: match DIRECTIONS.iter().position(|d| d == m) {
+                    Some(0) => '^',
+                    Some(1) => 'v',
+                    Some(2) => '<',
+                    _ => '>',
+                },
+                changes: Self::record_step(&mut warehouse, m),
+            })
+            .collect();
+
+        Self { initial, steps }
+    }
+
+    /// Moves the robot one step and returns the cells that changed as `(cell, before, after)`.
+    fn record_step(warehouse: &mut Warehouse, m: &Point) -> Vec<(Point, char, char)> {
+        let Some(pushed) = warehouse.pushed(m) else {
+            return vec![];
+        };
+
+        let cells = pushed
+            .iter()
+            .flat_map(|p| [*p, p + m])
+            .unique()
+            .map(|p| (p, warehouse.grid[p.y as usize][p.x as usize]))
+            .collect::<Vec<_>>();
+
+        warehouse.push(m, &pushed);
+
+        cells
+            .into_iter()
+            .map(|(p, before)| (p, before, warehouse.grid[p.y as usize][p.x as usize]))
+            .filter(|(_, before, after)| before != after)
+            .collect()
+    }
+
+    /// The warehouse after the first `n` moves.
+    fn state(&self, n: usize) -> Vec<Vec<char>> {
+        let mut grid = self.initial.clone();
+        for step in &self.steps[..n.min(self.steps.len())] {
+            for &(p, _, after) in &step.changes {
+                grid[p.y as usize][p.x as usize] = after;
+            }
+        }
+        grid
+    }
+
+    /// The cells that differ between the warehouse after `a` and after `b` moves, as
+    /// `(cell, at a, at b)` in reading order.
+    fn diff(&self, a: usize, b: usize) -> Vec<(Point, char, char)> {
+        let (grid_a, grid_b) = (self.state(a), self.state(b));
+
+        (0..grid_a.len())
+            .cartesian_product(0..grid_a[0].len())
+            .filter(|&(y, x)| grid_a[y][x] != grid_b[y][x])
+            .map(|(y, x)| {
+                let p = Point {
+                    x: x as isize,
+                    y: y as isize,
+                };
+                (p, grid_a[y][x], grid_b[y][x])
+            })
+            .collect()
+    }
+
+    /// Draws a warehouse. With `color`, the robot is yellow and the `highlight`ed cells are red.
+    fn render(grid: &[Vec<char>], highlight: &[Point], color: bool) -> String {
+        let mut out = String::new();
+
+        for (y, row) in grid.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                let p = Point {
+                    x: x as isize,
+                    y: y as isize,
+                };
+                let style = match cell {
+                    _ if !color => "",
+                    _ if highlight.contains(&p) => ANSI_CHANGED,
+                    '@' => ANSI_ROBOT,
+                    '#' => ANSI_WALL,
+                    _ => "",
+                };
+
+                if style.is_empty() {
+                    out.push(cell);
+                } else {
+                    out.push_str(&format!("{style}{cell}{ANSI_RESET}"));
+                }
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Plays the moves in `range` as an animation in the terminal, redrawing the warehouse after
+    /// every move and highlighting the cells that it changed.
+    fn animate(
+        &self,
+        out: &mut impl Write,
+        range: Range<usize>,
+        delay: Duration,
+    ) -> io::Result<()> {
+        let mut grid = self.state(range.start);
+
+        for n in range.start..range.end.min(self.steps.len()) {
+            let step = &self.steps[n];
+            for &(p, _, after) in &step.changes {
+                grid[p.y as usize][p.x as usize] = after;
+            }
+
+            let changed = step.changes.iter().map(|c| c.0).collect::<Vec<_>>();
+            writeln!(
+                out,
+                "{ANSI_CLEAR}{}step {}/{}: {}",
+                Self::render(&grid, &changed, true),
+                n + 1,
+                self.steps.len(),
+                step.direction
+            )?;
+            out.flush()?;
+            thread::sleep(delay);
+        }
+
+        Ok(())
+    }
+}
+
+const ANSI_CLEAR: &str = "\x1b[H\x1b[2J";
+const ANSI_CHANGED: &str = "\x1b[1;31m";
+const ANSI_ROBOT: &str = "\x1b[1;33m";
+const ANSI_WALL: &str = "\x1b[90m";
+
+/// The value after `name` in the arguments of the solution.
+fn arg(name: &str) -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let i = args.iter().position(|arg| arg == name)?;
+    args.get(i + 1).cloned()
+}
+
+/// Replays the moves instead of solving the day, when it is run with one of:
+/// - `--step <n>` to print the warehouse after `n` moves,
+/// - `--diff <a>,<b>` to list the cells that changed between moves `a` and `b`,
+/// - `--replay [<delay ms>]` to play all moves as an animation in the terminal.
+///
+/// Use `--part` to pick the narrow or the wide warehouse, e.g.
+/// `cargo run --bin 15 -- --part 2 --step 100`.
+fn inspect(input: &str) -> bool {
+    let has_flag = |name: &str| env::args().any(|arg| arg == name);
+    let replay_arg = has_flag("--replay");
+    // a flag without a value is reported below, like an invalid value
+    let step = has_flag("--step").then(|| arg("--step").unwrap_or_default());
+    let diff = has_flag("--diff").then(|| arg("--diff").unwrap_or_default());
+    if !replay_arg && step.is_none() && diff.is_none() {
+        return false;
+    }
+
+    let step = match step.map(|n| n.parse::<usize>().map_err(|_| n)) {
+        Some(Err(n)) => {
+            eprintln!("`--step` expects a number of moves, got `{n}`");
+            return true;
+        }
+        step => step.and_then(Result::ok),
+    };
+
+    let parse_diff = |d: &str| {
+        let (a, b) = d.split_once(',')?;
+        Some((a.parse::<usize>().ok()?, b.parse::<usize>().ok()?))
+    };
+    let diff = match diff.map(|d| parse_diff(&d).ok_or(d)) {
+        Some(Err(d)) => {
+            eprintln!("`--diff` expects two numbers of moves like `10,20`, got `{d}`");
+            return true;
+        }
+        diff => diff.and_then(Result::ok),
+    };
+
+    for part in [1, 2].into_iter().filter(|&part| is_part_selected(part)) {
+        let warehouse = match part {
+            1 => Warehouse::new(input),
+            _ => Warehouse::new(&widen(input)),
+        };
+        let replay = Replay::record(warehouse);
+
+        if let Some(n) = step {
+            // the cells that the n-th move changed
+            let highlight = n
+                .checked_sub(1)
+                .and_then(|i| replay.steps.get(i))
+                .map_or(vec![], |step| step.changes.iter().map(|c| c.0).collect());
+            print!("{}", Replay::render(&replay.state(n), &highlight, true));
+        }
+
+        if let Some((a, b)) = diff {
+            for (p, before, after) in replay.diff(a, b) {
+                println!("({}, {}): {before} -> {after}", p.x, p.y);
+            }
+        }
+
+        if replay_arg {
+            let delay = arg("--replay").and_then(|ms| ms.parse().ok()).unwrap_or(50);
+            let range = 0..replay.steps.len();
+            if let Err(e) = replay.animate(&mut io::stdout(), range, Duration::from_millis(delay)) {
+                eprintln!("could not play the replay: {e}");
+            }
+        }
+    }
+
+    true
+}
+
+/// Doubles the width of the warehouse for part two, keeping the moves.
+fn widen(input: &str) -> String {
     let mut wide_input = input
         .lines()
         .take_while(|line| !line.is_empty())
@@ -171,8 +424,16 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     wide_input.push_str("\n\n");
     wide_input.push_str(&moves);
+    wide_input
+}
 
-    let mut warehouse = Warehouse::new(&wide_input);
+pub fn part_one(input: &str) -> Option<usize> {
+    let mut warehouse = Warehouse::new(input);
+    Some(warehouse.solve())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let mut warehouse = Warehouse::new(&widen(input));
     Some(warehouse.solve())
 }
 
@@ -181,22 +442,55 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one_small() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(2028));
+    fn replays_every_step() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let replay = Replay::record(Warehouse::new(&input));
+        let mut warehouse = Warehouse::new(&input);
+        warehouse.solve();
+
+        assert_eq!(replay.steps.len(), warehouse.moves.len());
+        assert_eq!(replay.state(0), Warehouse::new(&input).grid);
+        assert_eq!(replay.state(replay.steps.len()), warehouse.grid);
+        assert!(replay.diff(7, 7).is_empty());
     }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(10_092));
+    fn records_wide_box_pushes() {
+        // the robot pushes two stacked wide boxes up, until the top box hits the wall
+        let input = "##########\n\
+            ##......##\n\
+            ##..[]..##\n\
+            ##.[][].##\n\
+            ##..@...##\n\
+            ##########\n\
+            \n\
+            ^^<";
+        let replay = Replay::record(Warehouse::new(input));
+
+        assert_eq!(replay.steps[0].changes.len(), 8);
+        assert!(replay.steps[1].changes.is_empty());
+        assert_eq!(
+            Replay::render(&replay.state(3), &[], false),
+            "##########\n\
+            ##..[]..##\n\
+            ##.[]...##\n\
+            ##.@.[].##\n\
+            ##......##\n\
+            ##########\n"
+        );
+        assert_eq!(replay.diff(1, 3).len(), 2);
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(9021));
+    fn animates_steps() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let replay = Replay::record(Warehouse::new(&input));
+
+        let mut out = vec![];
+        replay.animate(&mut out, 0..3, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(out.matches(ANSI_CLEAR).count(), 3);
+        assert!(out.contains(&format!("step 3/{}: v", replay.steps.len())));
     }
 }
@@ -1,5 +1,6 @@
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, shared, inspect);
 
+use advent_of_code::template::{ANSI_BOLD, ANSI_RESET};
 use std::collections::VecDeque;
 use std::ops::{Add, Index, IndexMut, Sub};
 
@@ -81,7 +82,16 @@ impl<T> IndexMut<Point> for Grid<T> {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// The result of the search in part one, reused by part two to trace back the best paths.
+pub struct Search {
+    grid: Grid<char>,
+    start: Point,
+    end: Point,
+    seen: Grid<[u32; 4]>,
+    lowest: u32,
+}
+
+pub fn part_one(input: &str) -> (Option<u32>, Search) {
     let grid = Grid::new(input);
     let start = grid.find('S').unwrap();
     let end = grid.find('E').unwrap();
@@ -128,81 +138,74 @@ pub fn part_one(input: &str) -> Option<u32> {
         cost += 1;
     }
 
-    Some(lowest)
+    let search = Search {
+        grid,
+        start,
+        end,
+        seen,
+        lowest,
+    };
+
+    (Some(lowest), search)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let grid = Grid::new(input);
-    let start = grid.find('S').unwrap();
-    let end = grid.find('E').unwrap();
+impl Search {
+    /// Marks the tiles that are part of at least one best path.
+    fn best_tiles(&self) -> Grid<bool> {
+        let Search {
+            grid,
+            start,
+            end,
+            lowest,
+            ..
+        } = self;
+        let (start, end, lowest) = (*start, *end, *lowest);
+
+        // the backwards search marks visited states, so it works on a copy of the costs.
+        let mut seen = Grid {
+            vec: self.seen.vec.clone(),
+            width: grid.width,
+            height: grid.height,
+        };
 
-    // Dijkstra's algorithm
-    let mut buckets = vec![Vec::new(); 1001];
-    let mut seen = Grid {
-        vec: vec![[u32::MAX; 4]; grid.vec.len()],
-        width: grid.width,
-        height: grid.height,
-    };
-    let mut cost = 0;
-    let mut lowest = u32::MAX;
+        // Backwards BFS to find a good seat
+        let mut todo = VecDeque::new();
+        let mut path = Grid {
+            vec: vec![false; grid.vec.len()],
+            width: grid.width,
+            height: grid.height,
+        };
 
-    buckets[0].push((start, 0));
-    seen[start][0] = 0;
+        for direction in 0..4 {
+            if seen[end][direction] == lowest {
+                todo.push_back((end, direction, lowest));
+            }
+        }
 
-    while lowest == u32::MAX {
-        let index = (cost % 1001) as usize;
+        while let Some((pos, dir, cost)) = todo.pop_front() {
+            path[pos] = true;
 
-        while let Some((point, direction)) = buckets[index].pop() {
-            if point == end {
-                lowest = cost;
-                break;
+            if pos == start {
+                continue;
             }
 
-            let left = (direction + 3) % 4;
-            let right = (direction + 1) % 4;
-            let next = [
-                (point + &DIRECTIONS[direction], direction, cost + 1),
-                (point, left, cost + 1000),
-                (point, right, cost + 1000),
-            ];
-
-            for (next_pos, next_dir, next_cost) in next {
-                if grid[next_pos] != '#' && next_cost < seen[next_pos][next_dir] {
-                    let index = (next_cost % 1001) as usize;
-                    buckets[index].push((next_pos, next_dir));
-                    seen[next_pos][next_dir] = next_cost;
+            for (next_pos, next_dir, next_cost) in Self::previous(pos, dir, cost) {
+                if next_cost == seen[next_pos][next_dir] {
+                    todo.push_back((next_pos, next_dir, next_cost));
+                    seen[next_pos][next_dir] = u32::MAX;
                 }
             }
         }
 
-        cost += 1;
+        path
     }
 
-    // Backwards BFS to find a good seat
-    let mut todo = VecDeque::new();
-    let mut path = Grid {
-        vec: vec![false; grid.vec.len()],
-        width: grid.width,
-        height: grid.height,
-    };
-
-    for direction in 0..4 {
-        if seen[end][direction] == lowest {
-            todo.push_back((end, direction, lowest));
-        }
-    }
-
-    while let Some((pos, dir, cost)) = todo.pop_front() {
-        path[pos] = true;
-
-        if pos == start {
-            continue;
-        }
-
+    /// The states that can lead to `(pos, dir)` at `cost`, i.e. one step back or a turn back.
+    fn previous(pos: Point, dir: usize, cost: u32) -> Vec<(Point, usize, u32)> {
         // Reverse direction and subtract cost
         let left = (dir + 3) % 4;
         let right = (dir + 1) % 4;
-        let next = if cost > 1000 {
+        if cost >= 1000 {
             vec![
                 (pos - &DIRECTIONS[dir], dir, cost - 1),
                 (pos, left, cost - 1000),
@@ -210,32 +213,278 @@ pub fn part_two(input: &str) -> Option<usize> {
             ]
         } else {
             vec![(pos - &DIRECTIONS[dir], dir, cost - 1)]
-        };
+        }
+    }
 
-        for (next_pos, next_dir, next_cost) in next {
-            if next_cost == seen[next_pos][next_dir] {
-                todo.push_back((next_pos, next_dir, next_cost));
-                seen[next_pos][next_dir] = u32::MAX;
+    /// Up to `limit` best paths from the start to the end. There can be a lot of them, as every
+    /// junction where two best paths meet doubles their number.
+    fn paths(&self, limit: usize) -> Vec<Path> {
+        let mut paths = vec![];
+        let mut states = vec![];
+
+        for direction in 0..4 {
+            if self.seen[self.end][direction] == self.lowest {
+                self.trace_back(
+                    self.end,
+                    direction,
+                    self.lowest,
+                    &mut states,
+                    &mut paths,
+                    limit,
+                );
             }
         }
+
+        paths
+    }
+
+    fn trace_back(
+        &self,
+        pos: Point,
+        dir: usize,
+        cost: u32,
+        states: &mut Vec<(Point, usize)>,
+        paths: &mut Vec<Path>,
+        limit: usize,
+    ) {
+        if paths.len() == limit {
+            return;
+        }
+
+        states.push((pos, dir));
+
+        if cost == 0 {
+            // the reindeer starts facing east
+            if (pos, dir) == (self.start, 0) {
+                paths.push(Path {
+                    states: states.iter().rev().copied().collect(),
+                });
+            }
+        } else {
+            for (prev_pos, prev_dir, prev_cost) in Self::previous(pos, dir, cost) {
+                if self.grid[prev_pos] != '#' && self.seen[prev_pos][prev_dir] == prev_cost {
+                    self.trace_back(prev_pos, prev_dir, prev_cost, states, paths, limit);
+                }
+            }
+        }
+
+        states.pop();
+    }
+
+    /// Draws the maze with the best tiles in green and the moves of `path` as arrows. Without
+    /// `color`, best tiles are drawn as `O` instead.
+    fn render(&self, path: Option<&Path>, color: bool) -> String {
+        let best = self.best_tiles();
+        let arrows = path.map(Path::arrows).unwrap_or_default();
+        let mut out = String::new();
+
+        for y in 0..self.grid.height {
+            for x in 0..self.grid.width - 1 {
+                let point = Point {
+                    x: x as isize,
+                    y: y as isize,
+                };
+                let tile = self.grid[point];
+                let arrow = arrows.iter().rev().find(|(p, _)| *p == point);
+
+                let (symbol, style) = match (tile, arrow) {
+                    ('S' | 'E', _) => (tile, ANSI_BOLD),
+                    (_, Some((_, arrow))) => (*arrow, ANSI_ARROW),
+                    _ if best[point] => (if color { '.' } else { 'O' }, ANSI_BEST),
+                    ('#', _) => (tile, ANSI_WALL),
+                    _ => (tile, ""),
+                };
+
+                if color && !style.is_empty() {
+                    out.push_str(&format!("{style}{symbol}{ANSI_RESET}"));
+                } else {
+                    out.push(symbol);
+                }
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Draws the maze as an SVG image, with the best tiles in green and `path` as a line.
+    fn to_svg(&self, path: Option<&Path>) -> String {
+        const TILE: usize = 10;
+        let best = self.best_tiles();
+        let (width, height) = ((self.grid.width - 1) * TILE, self.grid.height * TILE);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\">\n\
+            <rect width=\"{width}\" height=\"{height}\" fill=\"white\"/>\n"
+        );
+
+        for y in 0..self.grid.height {
+            for x in 0..self.grid.width - 1 {
+                let point = Point {
+                    x: x as isize,
+                    y: y as isize,
+                };
+                let fill = match self.grid[point] {
+                    '#' => "#444",
+                    'S' | 'E' => "#d33",
+                    _ if best[point] => "#8d8",
+                    _ => continue,
+                };
+                svg.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{TILE}\" height=\"{TILE}\" fill=\"{fill}\"/>\n",
+                    x * TILE,
+                    y * TILE
+                ));
+            }
+        }
+
+        if let Some(path) = path {
+            let points = path
+                .states
+                .iter()
+                .map(|(p, _)| format!("{},{}", p.x * TILE as isize + 5, p.y * TILE as isize + 5))
+                .collect::<Vec<_>>()
+                .join(" ");
+            svg.push_str(&format!(
+                "<polyline points=\"{points}\" fill=\"none\" stroke=\"#26c\" stroke-width=\"3\"/>\n"
+            ));
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+const ANSI_BEST: &str = "\x1b[32m";
+const ANSI_ARROW: &str = "\x1b[1;33m";
+const ANSI_WALL: &str = "\x1b[90m";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Move {
+    Forward,
+    Left,
+    Right,
+}
+
+/// A best path, as the states of the reindeer from the start to the end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Path {
+    states: Vec<(Point, usize)>,
+}
+
+impl Path {
+    fn moves(&self) -> Vec<Move> {
+        self.states
+            .windows(2)
+            .map(|pair| match (pair[1].1 + 4 - pair[0].1) % 4 {
+                0 => Move::Forward,
+                1 => Move::Right,
+                _ => Move::Left,
+            })
+            .collect()
+    }
+
+    /// The moves with runs of steps forward combined, e.g. `F3 L F2`.
+    fn turns(&self) -> String {
+        self.moves()
+            .chunk_by(|a, b| a == b && *a == Move::Forward)
+            .map(|run| match run[0] {
+                Move::Forward => format!("F{}", run.len()),
+                Move::Left => "L".into(),
+                Move::Right => "R".into(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 
-    Some(path.vec.iter().filter(|&&x| x).count())
+    /// The direction the reindeer leaves each tile in, as an arrow.
+    fn arrows(&self) -> Vec<(Point, char)> {
+        self.states
+            .iter()
+            .map(|&(point, dir)| (point, ['>', 'v', '<', '^'][dir]))
+            .collect()
+    }
+}
+
+/// Shows a best path instead of solving the day, when it is run with `--render` (colored, in the
+/// terminal) or `--svg <file>`, e.g. `cargo run --bin 16 -- --render`.
+fn inspect(input: &str) -> bool {
+    let args: Vec<String> = std::env::args().collect();
+    let render = args.iter().any(|arg| arg == "--render");
+    let svg = args
+        .iter()
+        .position(|arg| arg == "--svg")
+        .map(|i| args.get(i + 1));
+
+    if !render && svg.is_none() {
+        return false;
+    }
+
+    let search = part_one(input).1;
+    let paths = search.paths(1);
+    let path = paths.first();
+
+    if render {
+        print!("{}", search.render(path, true));
+        if let Some(path) = path {
+            println!("{}", path.turns());
+        }
+    }
+
+    match svg {
+        Some(Some(file)) => {
+            if let Err(e) = std::fs::write(file, search.to_svg(path)) {
+                eprintln!("could not write {file}: {e}");
+            }
+        }
+        Some(None) => eprintln!("`--svg` expects a file name"),
+        None => {}
+    }
+
+    true
+}
+
+pub fn part_two(_input: &str, search: &Search) -> Option<usize> {
+    Some(search.best_tiles().vec.iter().filter(|&&x| x).count())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn search() -> Search {
+        part_one(&advent_of_code::template::read_file("examples", DAY)).1
+    }
+
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7036));
+    fn extracts_best_paths() {
+        let search = search();
+        let paths = search.paths(usize::MAX);
+        assert_eq!(paths.len(), 3);
+
+        for path in &paths {
+            let moves = path.moves();
+            let turns = moves.iter().filter(|&&m| m != Move::Forward).count();
+            let steps = moves.len() - turns;
+            assert_eq!(steps as u32 + 1000 * turns as u32, search.lowest);
+            assert_eq!(path.states.first(), Some(&(search.start, 0)));
+            assert_eq!(path.states.last().map(|s| s.0), Some(search.end));
+        }
+
+        assert_eq!(search.paths(2).len(), 2);
+        assert_eq!(paths[0].turns(), "L F2 R F2 L F4 R F8 R F6 L F2 L F12");
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(45));
+    fn renders_best_paths() {
+        let search = search();
+        let paths = search.paths(1);
+        let rendered = search.render(paths.first(), false);
+
+        assert_eq!(rendered.lines().count(), 15);
+        assert_eq!(rendered.lines().nth(13), Some("#S..#.....#>>^#"));
+        assert!(rendered.contains('^') && rendered.contains('O'));
+        assert!(search
+            .to_svg(paths.first())
+            .contains("<polyline points=\"15,135 "));
     }
 }
@@ -198,7 +198,7 @@ fn has_valid_path(grid: &Grid<char>) -> bool {
             };
             if grid[p] == '.' {
                 for dir in DIRECTIONS {
-                    union(&grid, &mut parent, p, &dir);
+                    union(grid, &mut parent, p, &dir);
                 }
             }
         }
@@ -241,20 +241,3 @@ pub fn part_two(input: &str) -> Option<String> {
 
     None
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(22));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("6,1".to_string()));
-    }
-}
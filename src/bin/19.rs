@@ -51,27 +51,10 @@ pub fn part_two(input: &str) -> Option<usize> {
             let combs = combinations(l, &towels, &mut memo);
             match combs {
                 0 => None,
-                _ => Some(combs as usize),
+                _ => Some(combs),
             }
         })
         .sum();
 
     Some(res)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(16));
-    }
-}
@@ -1,188 +1,222 @@
-advent_of_code::solution!(21);
+advent_of_code::solution!(21, inspect);
 
 use std::collections::{HashMap, VecDeque};
-use std::rc::Rc;
-
-const DIRS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
-const NUMERIC: [[u8; 3]; 4] = [
-    [b'7', b'8', b'9'],
-    [b'4', b'5', b'6'],
-    [b'1', b'2', b'3'],
-    [b' ', b'0', b'A'],
+
+const NUMERIC: &str = "789\n456\n123\n 0A";
+const DIRECTIONAL: &str = " ^A\n<v>";
+
+const MOVES: [((i32, i32), u8); 4] = [
+    ((1, 0), b'>'),
+    ((0, 1), b'v'),
+    ((-1, 0), b'<'),
+    ((0, -1), b'^'),
 ];
-const DIRECTIONAL: [[u8; 3]; 2] = [[b' ', b'^', b'A'], [b'<', b'v', b'>']];
-
-fn find_shortest_paths(
-    keypad: &[[u8; 3]],
-    from: u8,
-    to: u8,
-    cache: &mut HashMap<(u8, u8), Rc<Vec<Vec<u8>>>>,
-) -> Rc<Vec<Vec<u8>>> {
-    if let Some(cached) = cache.get(&(from, to)) {
-        return cached.clone();
-    }
 
-    if from == to {
-        let result = Rc::new(vec![vec![b'A']]);
-        cache.insert((from, to), result.clone());
-        return result;
+/// A keypad layout of any size. Every character is a key, spaces are gaps the arm must avoid.
+#[derive(Clone)]
+struct Keypad {
+    keys: HashMap<u8, (i32, i32)>,
+    positions: HashMap<(i32, i32), u8>,
+}
+
+impl Keypad {
+    fn parse(layout: &str) -> Self {
+        let mut keys = HashMap::new();
+        let mut positions = HashMap::new();
+
+        for (y, row) in layout.lines().enumerate() {
+            for (x, key) in row.bytes().enumerate() {
+                if key == b' ' {
+                    continue;
+                }
+
+                let pos = (x as i32, y as i32);
+                assert!(
+                    keys.insert(key, pos).is_none(),
+                    "key {} appears twice",
+                    key as char
+                );
+                positions.insert(pos, key);
+            }
+        }
+
+        assert!(keys.contains_key(&b'A'), "keypads need an `A` key");
+        Self { keys, positions }
     }
 
-    // find 'from' and 'to' on the keypad
-    let mut start = (0, 0);
-    let mut end = (0, 0);
-    for (y, row) in keypad.iter().enumerate() {
-        for (x, &key) in row.iter().enumerate() {
-            if key == from {
-                start = (x, y);
+    /// All shortest ways to move the arm from `from` to `to` and press it, e.g. `<^A`.
+    fn paths(&self, from: u8, to: u8) -> Vec<Vec<u8>> {
+        let (start, end) = (self.keys[&from], self.keys[&to]);
+
+        // flood fill from the end, so every step of a shortest path gets closer to it
+        let mut dists = HashMap::from([(end, 0)]);
+        let mut queue = VecDeque::from([end]);
+        while let Some(pos) = queue.pop_front() {
+            for ((dx, dy), _) in MOVES {
+                let next = (pos.0 + dx, pos.1 + dy);
+                if self.positions.contains_key(&next) && !dists.contains_key(&next) {
+                    dists.insert(next, dists[&pos] + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut paths = vec![];
+        let mut stack = vec![(start, vec![])];
+        while let Some((pos, mut path)) = stack.pop() {
+            if pos == end {
+                path.push(b'A');
+                paths.push(path);
+                continue;
             }
-            if key == to {
-                end = (x, y);
+
+            for ((dx, dy), key) in MOVES {
+                let next = (pos.0 + dx, pos.1 + dy);
+                if dists.get(&next) == Some(&(dists[&pos] - 1)) {
+                    let mut path = path.clone();
+                    path.push(key);
+                    stack.push((next, path));
+                }
             }
         }
+
+        paths
     }
+}
 
-    // flood fill keypad to find shortest path
-    let mut dists = vec![vec![usize::MAX; 3]; keypad.len()];
-    let mut queue = VecDeque::new();
-    queue.push_back((start, 0));
-
-    while let Some((pos, dist)) = queue.pop_front() {
-        dists[pos.1][pos.0] = dist;
-        for (dx, dy) in DIRS {
-            let nx = pos.0 as i32 + dx;
-            let ny = pos.1 as i32 + dy;
-            if nx >= 0
-                && ny >= 0
-                && nx < 3
-                && ny < keypad.len() as i32
-                && keypad[ny as usize][nx as usize] != b' '
-                && dists[ny as usize][nx as usize] == usize::MAX
-            {
-                queue.push_back(((nx as usize, ny as usize), dist + 1));
-            }
+/// A chain of keypads: robots type on each keypad, controlled from the directional keypad after
+/// it. You type on the directional keypad of the last robot.
+struct Chain {
+    keypads: Vec<Keypad>,
+    /// The fewest presses that you need to move the arm of a layer between two keys and press
+    /// the second one, along with how the arm of the next layer moves.
+    best: HashMap<(usize, u8, u8), (usize, Vec<u8>)>,
+}
+
+impl Chain {
+    fn new(door: Keypad, directional: &Keypad, robots: usize) -> Self {
+        let mut keypads = vec![door];
+        keypads.extend((0..robots).map(|_| directional.clone()));
+
+        Self {
+            keypads,
+            best: HashMap::new(),
         }
     }
 
-    // backtrace and collect all paths
-    let mut paths = vec![];
-    let mut stack = vec![(end, vec![b'A'])];
-    while let Some((pos, path)) = stack.pop() {
-        if pos == start {
-            paths.push(path);
-            continue;
+    fn press(&mut self, layer: usize, from: u8, to: u8) -> usize {
+        if let Some((presses, _)) = self.best.get(&(layer, from, to)) {
+            return *presses;
         }
 
-        for (i, (dx, dy)) in DIRS.iter().enumerate() {
-            let nx = pos.0 as i32 + dx;
-            let ny = pos.1 as i32 + dy;
-            if nx >= 0
-                && ny >= 0
-                && nx < 3
-                && ny < keypad.len() as i32
-                && dists[ny as usize][nx as usize] < dists[pos.1][pos.0]
-            {
-                let c = match i {
-                    0 => b'<',
-                    1 => b'^',
-                    2 => b'>',
-                    3 => b'v',
-                    _ => unreachable!(),
+        let best = self.keypads[layer]
+            .paths(from, to)
+            .into_iter()
+            .map(|path| {
+                let presses = if layer + 1 == self.keypads.len() {
+                    path.len()
+                } else {
+                    self.presses(layer + 1, &path)
                 };
-                let mut new_path = vec![c];
-                new_path.extend(&path);
-                stack.push(((nx as usize, ny as usize), new_path));
+                (presses, path)
+            })
+            .min()
+            .unwrap();
+
+        let presses = best.0;
+        self.best.insert((layer, from, to), best);
+        presses
+    }
+
+    /// The fewest presses that you need to type `keys` on the keypad of `layer`. All arms start
+    /// at `A`, and are back at `A` after every key of the previous layer.
+    fn presses(&mut self, layer: usize, keys: &[u8]) -> usize {
+        let mut from = b'A';
+        keys.iter()
+            .map(|&key| {
+                let presses = self.press(layer, from, key);
+                from = key;
+                presses
+            })
+            .sum()
+    }
+
+    /// The keys that are pressed on every layer to type `code` with the fewest presses, starting
+    /// with `code` itself and ending with your presses. These grow exponentially with the number
+    /// of robots.
+    fn sequences(&mut self, code: &[u8]) -> Vec<Vec<u8>> {
+        self.presses(0, code);
+
+        let mut sequences = vec![code.to_vec()];
+        for layer in 0..self.keypads.len() {
+            let keys = sequences.last().unwrap();
+            let mut from = b'A';
+            let mut next = vec![];
+
+            for &key in keys {
+                next.extend(&self.best[&(layer, from, key)].1);
+                from = key;
             }
+            sequences.push(next);
         }
+
+        sequences
     }
+}
 
-    let result = Rc::new(paths);
-    cache.insert((from, to), result.clone());
-    result
+/// The sum of the complexities of the codes in `input`, each typed on the `door` keypad through
+/// a chain of `robots` robots at `directional` keypads. Layouts are given row by row like
+/// [`NUMERIC`], with a space for every gap.
+fn complexity(input: &str, door: &str, directional: &str, robots: usize) -> usize {
+    let mut chain = Chain::new(Keypad::parse(door), &Keypad::parse(directional), robots);
+
+    input
+        .lines()
+        .map(|code| {
+            let presses = chain.presses(0, code.as_bytes());
+            let n = code[0..3].parse::<usize>().unwrap();
+            presses * n
+        })
+        .sum()
 }
 
-fn find_shortest_sequence(
-    s: &[u8],
-    depth: usize,
-    highest: bool,
-    cursors: &mut Vec<u8>,
-    cache: &mut HashMap<(Vec<u8>, usize, u8), usize>,
-    path_cache: &mut HashMap<(u8, u8), Rc<Vec<Vec<u8>>>>,
-) -> usize {
-    let cache_key = (s.to_vec(), depth, cursors[depth]);
-    if let Some(cached) = cache.get(&cache_key) {
-        return *cached;
+/// Prints the keys that are pressed on every layer to type each code instead of solving the
+/// day, when it is run with `--sequences`, e.g. `cargo run --bin 21 -- --sequences`.
+/// `--robots <n>` sets the number of robots, 2 by default.
+fn inspect(input: &str) -> bool {
+    let args: Vec<String> = std::env::args().collect();
+    if !args.iter().any(|arg| arg == "--sequences") {
+        return false;
     }
 
-    let mut result = 0;
-    s.iter().for_each(|c| {
-        let paths = find_shortest_paths(
-            if highest { &NUMERIC } else { &DIRECTIONAL },
-            cursors[depth],
-            *c,
-            path_cache,
-        );
-        if depth == 0 {
-            // all paths are the same length
-            result += paths[0].len();
-        } else {
-            result += paths
-                .iter()
-                .map(|p| find_shortest_sequence(p, depth - 1, false, cursors, cache, path_cache))
-                .min()
-                .unwrap();
+    let robots = match args.iter().position(|arg| arg == "--robots") {
+        None => 2,
+        Some(i) => match args.get(i + 1).and_then(|n| n.parse().ok()) {
+            Some(robots) => robots,
+            None => {
+                eprintln!("`--robots` expects a number of robots");
+                return true;
+            }
+        },
+    };
+
+    let mut chain = Chain::new(Keypad::parse(NUMERIC), &Keypad::parse(DIRECTIONAL), robots);
+    for code in input.lines() {
+        for sequence in chain.sequences(code.as_bytes()) {
+            println!("{}", String::from_utf8_lossy(&sequence));
         }
-        cursors[depth] = *c;
-    });
+        println!();
+    }
 
-    cache.insert(cache_key, result);
-    result
+    true
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let max_depth = 2;
-    let mut cache = HashMap::new();
-    let mut path_cache = HashMap::new();
-
-    let result = input.lines().fold(0, |acc, code| {
-        let mut cursors = vec![b'A'; max_depth + 1];
-        let len = find_shortest_sequence(
-            code.as_bytes(),
-            max_depth,
-            true,
-            &mut cursors,
-            &mut cache,
-            &mut path_cache,
-        );
-
-        let n = code[0..3].parse::<usize>().unwrap();
-        acc + (len * n)
-    });
-
-    Some(result)
+    Some(complexity(input, NUMERIC, DIRECTIONAL, 2))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let max_depth = 25;
-    let mut cache = HashMap::new();
-    let mut path_cache = HashMap::new();
-
-    let result = input.lines().fold(0, |acc, code| {
-        let mut cursors = vec![b'A'; max_depth + 1];
-        let len = find_shortest_sequence(
-            code.as_bytes(),
-            max_depth,
-            true,
-            &mut cursors,
-            &mut cache,
-            &mut path_cache,
-        );
-
-        let n = code[0..3].parse::<usize>().unwrap();
-        acc + (len * n)
-    });
-
-    Some(result)
+    Some(complexity(input, NUMERIC, DIRECTIONAL, 25))
 }
 
 #[cfg(test)]
@@ -190,14 +224,51 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(126_384));
+    fn shows_the_sequence_of_every_layer() {
+        let mut chain = Chain::new(Keypad::parse(NUMERIC), &Keypad::parse(DIRECTIONAL), 2);
+        let sequences = chain.sequences(b"029A");
+
+        assert_eq!(sequences.len(), 4);
+        assert_eq!(sequences[0], b"029A");
+        assert_eq!(sequences[1].len(), b"<A^A>^^AvvvA".len());
+        assert_eq!(sequences[3].len(), 68);
+
+        // typing each layer on its keypad results in the layer before it
+        for (layer, keypad) in chain.keypads.iter().enumerate() {
+            assert_eq!(type_on(keypad, &sequences[layer + 1]), sequences[layer]);
+        }
+    }
+
+    #[test]
+    fn supports_other_keypads() {
+        // a wide keypad with a gap in the middle, typed on directly
+        let keypad = Keypad::parse("ab c\nd  A\nefgh");
+        assert_eq!(keypad.paths(b'A', b'a').len(), 1);
+        assert_eq!(keypad.paths(b'A', b'a')[0], b"v<<<^^A");
+
+        let mut chain = Chain::new(keypad, &Keypad::parse(DIRECTIONAL), 0);
+        assert_eq!(chain.presses(0, b"aA"), 14);
+
+        // without robots, the door keypad is typed on directly
+        assert_eq!(complexity("029A", NUMERIC, DIRECTIONAL, 0), 12 * 29);
     }
 
-    // #[test]
-    // fn test_part_two() {
-    //     let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-    //     assert_eq!(result, None);
-    // }
+    /// Moves the arm over `keypad` as instructed by `presses` and returns the pressed keys.
+    fn type_on(keypad: &Keypad, presses: &[u8]) -> Vec<u8> {
+        let mut pos = keypad.keys[&b'A'];
+        let mut keys = vec![];
+
+        for &press in presses {
+            if press == b'A' {
+                keys.push(keypad.positions[&pos]);
+                continue;
+            }
+
+            let ((dx, dy), _) = MOVES.iter().find(|(_, key)| *key == press).unwrap();
+            pos = (pos.0 + dx, pos.1 + dy);
+            assert!(keypad.positions.contains_key(&pos), "the arm is over a gap");
+        }
+
+        keys
+    }
 }
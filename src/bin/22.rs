@@ -88,7 +88,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     let mut sum_map = HashMap::<_, usize>::new();
     best_price_sequences.iter().for_each(|sequence| {
         for (key, &value) in sequence.iter() {
-            let old_value = sum_map.entry(key.clone()).or_insert(0);
+            let old_value = sum_map.entry(*key).or_insert(0);
             *old_value += value as usize;
             if *old_value > best_total {
                 best_total = *old_value;
@@ -98,22 +98,3 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     Some(best_total)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(37_327_623));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(23));
-    }
-}
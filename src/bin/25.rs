@@ -1,4 +1,4 @@
-advent_of_code::solution!(25);
+advent_of_code::solution!(25, 1);
 
 use itertools::Itertools;
 use std::str::FromStr;
@@ -63,20 +63,3 @@ pub fn part_one(input: &str) -> Option<u32> {
 // pub fn part_two(input: &str) -> Option<u32> {
 //     None
 // }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
-    }
-
-    // #[test]
-    // fn test_part_two() {
-    //     let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-    //     assert_eq!(result, None);
-    // }
-}
@@ -0,0 +1,403 @@
+/// Fixed-size sets of small integers, stored as bits.
+///
+/// The capacity of a [`BitSet`] is a multiple of 64 that is fixed at compile time, so sets can be
+/// copied around cheaply and set operations compile down to a few word operations. Use
+/// [`words_for`] to size a set for a given number of elements:
+///
+/// ```ignore
+/// // a set of two letter keys like `ab`, see day 23.
+/// type Keys = BitSet<{ words_for(26 * 26) }>;
+/// ```
+use std::fmt::Debug;
+
+/// The number of 64 bit words that are needed to store `bits` bits.
+#[must_use]
+pub const fn words_for(bits: usize) -> usize {
+    bits.div_ceil(64)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    /// The largest element that fits into the set, plus one.
+    pub const CAPACITY: usize = WORDS * 64;
+
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { words: [0; WORDS] }
+    }
+
+    /// Adds an element. Returns whether it was not in the set before.
+    /// Panics if the element does not fit into the set.
+    pub fn insert(&mut self, element: usize) -> bool {
+        let (word, bit) = Self::position(element);
+        let is_new = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        is_new
+    }
+
+    /// Removes an element. Returns whether it was in the set before.
+    pub fn remove(&mut self, element: usize) -> bool {
+        let (word, bit) = Self::position(element);
+        let was_present = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        was_present
+    }
+
+    #[must_use]
+    pub fn contains(&self, element: usize) -> bool {
+        element < Self::CAPACITY && {
+            let (word, bit) = Self::position(element);
+            self.words[word] & bit != 0
+        }
+    }
+
+    /// The number of elements in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// The smallest element of the set.
+    #[must_use]
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    /// The elements of the set in ascending order.
+    pub fn iter(&self) -> Iter<'_, WORDS> {
+        Iter {
+            words: &self.words,
+            index: 0,
+            current: self.words.first().copied().unwrap_or_default(),
+        }
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// The elements of `self` that are not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    /// The number of elements in both sets, without building the intersection.
+    #[must_use]
+    pub fn intersection_len(&self, other: &Self) -> usize {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    /// Keeps only the elements that are smaller than `end`.
+    #[must_use]
+    pub fn below(&self, end: usize) -> Self {
+        let mut set = *self;
+        for (i, word) in set.words.iter_mut().enumerate() {
+            let start = i * 64;
+            if end <= start {
+                *word = 0;
+            } else if end < start + 64 {
+                *word &= (1 << (end - start)) - 1;
+            }
+        }
+        set
+    }
+
+    fn zip_with(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        let mut words = self.words;
+        for (word, other) in words.iter_mut().zip(&other.words) {
+            *word = op(*word, *other);
+        }
+        Self { words }
+    }
+
+    fn position(element: usize) -> (usize, u64) {
+        assert!(
+            element < Self::CAPACITY,
+            "{element} does not fit into a set of {} elements",
+            Self::CAPACITY
+        );
+        (element / 64, 1 << (element % 64))
+    }
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> Debug for BitSet<WORDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for element in iter {
+            set.insert(element);
+        }
+        set
+    }
+}
+
+impl<'a, const WORDS: usize> IntoIterator for &'a BitSet<WORDS> {
+    type Item = usize;
+    type IntoIter = Iter<'a, WORDS>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterates over the elements of a [`BitSet`] in ascending order.
+pub struct Iter<'a, const WORDS: usize> {
+    words: &'a [u64; WORDS],
+    index: usize,
+    current: u64,
+}
+
+impl<const WORDS: usize> Iterator for Iter<'_, WORDS> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.index += 1;
+            self.current = *self.words.get(self.index)?;
+        }
+
+        let bit = self.current.trailing_zeros() as usize;
+        // clear the lowest set bit.
+        self.current &= self.current - 1;
+        Some(self.index * 64 + bit)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A square matrix of bits, e.g. the adjacency matrix of a graph. Every row is a [`BitSet`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct BitMatrix<const WORDS: usize> {
+    rows: Vec<BitSet<WORDS>>,
+}
+
+impl<const WORDS: usize> BitMatrix<WORDS> {
+    /// Creates a matrix with [`BitSet::CAPACITY`] rows and columns.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rows: vec![BitSet::new(); BitSet::<WORDS>::CAPACITY],
+        }
+    }
+
+    pub fn set(&mut self, row: usize, col: usize) {
+        self.rows[row].insert(col);
+    }
+
+    /// Sets both `(a, b)` and `(b, a)`, i.e. adds an undirected edge between `a` and `b`.
+    pub fn set_symmetric(&mut self, a: usize, b: usize) {
+        self.set(a, b);
+        self.set(b, a);
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.rows[row].contains(col)
+    }
+
+    #[must_use]
+    pub fn row(&self, row: usize) -> &BitSet<WORDS> {
+        &self.rows[row]
+    }
+
+    /// The rows that have at least one bit set.
+    #[must_use]
+    pub fn nonempty_rows(&self) -> BitSet<WORDS> {
+        (0..self.rows.len())
+            .filter(|&row| !self.rows[row].is_empty())
+            .collect()
+    }
+
+    /// The largest clique of the undirected graph with this adjacency matrix, see
+    /// [`Graph::maximum_clique`](crate::graph::Graph::maximum_clique). If there are several, the
+    /// first one that is found is returned.
+    #[must_use]
+    pub fn maximum_clique(&self) -> BitSet<WORDS> {
+        let mut best = BitSet::new();
+        self.bron_kerbosch(
+            BitSet::new(),
+            self.nonempty_rows(),
+            BitSet::new(),
+            &mut best,
+        );
+        best
+    }
+
+    /// Bron–Kerbosch with pivoting, keeping only the largest clique in `best` and skipping
+    /// branches that cannot beat it.
+    fn bron_kerbosch(
+        &self,
+        clique: BitSet<WORDS>,
+        mut candidates: BitSet<WORDS>,
+        mut excluded: BitSet<WORDS>,
+        best: &mut BitSet<WORDS>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() && clique.len() > best.len() {
+                *best = clique;
+            }
+            return;
+        }
+
+        if clique.len() + candidates.len() <= best.len() {
+            return;
+        }
+
+        let pivot = candidates
+            .union(&excluded)
+            .iter()
+            .max_by_key(|&node| self.row(node).intersection_len(&candidates))
+            .unwrap();
+
+        for node in &candidates.difference(self.row(pivot)) {
+            let mut next = clique;
+            next.insert(node);
+            self.bron_kerbosch(
+                next,
+                candidates.intersection(self.row(node)),
+                excluded.intersection(self.row(node)),
+                best,
+            );
+
+            candidates.remove(node);
+            excluded.insert(node);
+        }
+    }
+}
+
+impl<const WORDS: usize> Default for BitMatrix<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> Debug for BitMatrix<WORDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries((self.rows.iter().enumerate()).filter(|(_, row)| !row.is_empty()))
+            .finish()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{words_for, BitMatrix, BitSet};
+    use crate::{graph::Graph, property::Rng};
+
+    type Set = BitSet<{ words_for(200) }>;
+
+    #[test]
+    fn sizes_sets() {
+        assert_eq!(words_for(64), 1);
+        assert_eq!(words_for(65), 2);
+        assert_eq!(Set::CAPACITY, 256);
+    }
+
+    #[test]
+    fn inserts_and_removes() {
+        let mut set = Set::new();
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(199));
+        assert!(set.contains(199));
+        assert!(!set.contains(1000));
+        assert_eq!(set.len(), 2);
+
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.first(), Some(199));
+    }
+
+    #[test]
+    fn iterates_in_order() {
+        let set: Set = [130, 0, 64, 63, 255].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 63, 64, 130, 255]);
+        assert_eq!(Set::new().iter().next(), None);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a: Set = [1, 2, 3, 100].into_iter().collect();
+        let b: Set = [2, 3, 4, 200].into_iter().collect();
+
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(a.intersection_len(&b), 2);
+        assert_eq!(a.union(&b).len(), 6);
+        assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), [1, 100]);
+        assert_eq!(a.below(3).iter().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(a.below(64).len(), 3);
+        assert_eq!(a.below(1000), a);
+    }
+
+    #[test]
+    fn stores_matrices() {
+        let mut matrix = BitMatrix::<{ words_for(100) }>::new();
+        matrix.set_symmetric(3, 70);
+        matrix.set(5, 5);
+
+        assert!(matrix.get(70, 3));
+        assert!(!matrix.get(3, 5));
+        assert_eq!(matrix.row(3).iter().collect::<Vec<_>>(), [70]);
+        assert_eq!(
+            matrix.nonempty_rows().iter().collect::<Vec<_>>(),
+            [3, 5, 70]
+        );
+    }
+
+    #[test]
+    fn finds_the_largest_clique() {
+        let mut rng = Rng::new(23);
+
+        for _ in 0..20 {
+            let mut matrix = BitMatrix::<{ words_for(40) }>::new();
+            let mut graph = Graph::undirected(40);
+            for a in 0..40 {
+                for b in 0..a {
+                    if rng.chance(0.4) {
+                        matrix.set_symmetric(a, b);
+                        graph.add_edge(a, b);
+                    }
+                }
+            }
+
+            let clique: Vec<usize> = matrix.maximum_clique().iter().collect();
+            assert_eq!(clique.len(), graph.maximum_clique().len());
+            for (i, &a) in clique.iter().enumerate() {
+                assert!(clique[..i].iter().all(|&b| matrix.get(a, b)));
+            }
+        }
+    }
+}
@@ -0,0 +1,470 @@
+/// Graphs over the nodes `0..n`, stored as ordered adjacency lists.
+///
+/// Most algorithms return their results in ascending order of the nodes, so that answers built
+/// from them are deterministic. Puzzles that name their nodes can build a graph with
+/// [`Graph::from_labeled_edges`], which maps every label to a node.
+use std::{
+    cmp::Reverse,
+    collections::{btree_set, BTreeSet, BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Graph {
+    adjacency: Vec<BTreeSet<usize>>,
+    directed: bool,
+}
+
+impl Graph {
+    /// Creates an undirected graph with `n` nodes and no edges.
+    #[must_use]
+    pub fn undirected(n: usize) -> Self {
+        Self {
+            adjacency: vec![BTreeSet::new(); n],
+            directed: false,
+        }
+    }
+
+    /// Creates a directed graph with `n` nodes and no edges.
+    #[must_use]
+    pub fn directed(n: usize) -> Self {
+        Self {
+            adjacency: vec![BTreeSet::new(); n],
+            directed: true,
+        }
+    }
+
+    /// Creates a graph from edges between labels, e.g. the `kh-tc` lines of day 23.
+    /// Returns the graph along with the label of every node. Nodes are numbered in the order in
+    /// which their labels first appear.
+    pub fn from_labeled_edges<T: Copy + Eq + Hash>(
+        edges: impl IntoIterator<Item = (T, T)>,
+        directed: bool,
+    ) -> (Self, Vec<T>) {
+        let mut graph = if directed {
+            Self::directed(0)
+        } else {
+            Self::undirected(0)
+        };
+        let mut labels: Vec<T> = vec![];
+        let mut nodes: HashMap<T, usize> = HashMap::new();
+
+        let mut node = |label: T| {
+            *nodes.entry(label).or_insert_with(|| {
+                labels.push(label);
+                labels.len() - 1
+            })
+        };
+
+        for (a, b) in edges {
+            let (a, b) = (node(a), node(b));
+            graph.add_edge(a, b);
+        }
+
+        (graph, labels)
+    }
+
+    /// The number of nodes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    #[must_use]
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Adds an edge from `a` to `b`, and from `b` to `a` in an undirected graph.
+    /// The graph grows if a node does not exist yet.
+    pub fn add_edge(&mut self, a: usize, b: usize) {
+        let len = a.max(b) + 1;
+        if len > self.adjacency.len() {
+            self.adjacency.resize(len, BTreeSet::new());
+        }
+
+        self.adjacency[a].insert(b);
+        if !self.directed {
+            self.adjacency[b].insert(a);
+        }
+    }
+
+    #[must_use]
+    pub fn has_edge(&self, a: usize, b: usize) -> bool {
+        self.adjacency.get(a).is_some_and(|n| n.contains(&b))
+    }
+
+    /// The nodes that an edge of `node` leads to, in ascending order.
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adjacency[node].iter().copied()
+    }
+
+    #[must_use]
+    pub fn degree(&self, node: usize) -> usize {
+        self.adjacency[node].len()
+    }
+
+    /// All edges as `(from, to)`. Edges of an undirected graph are returned once, with `from <= to`.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.adjacency.iter().enumerate().flat_map(move |(a, n)| {
+            n.iter()
+                .copied()
+                .filter(move |&b| self.directed || a <= b)
+                .map(move |b| (a, b))
+        })
+    }
+
+    /* ------------------------------ undirected ------------------------------ */
+
+    /// All maximal cliques of an undirected graph, i.e. sets of pairwise connected nodes that
+    /// cannot be extended by another node. Uses Bron–Kerbosch with pivoting.
+    #[must_use]
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        let mut cliques = vec![];
+        self.bron_kerbosch(
+            &mut vec![],
+            self.nodes(),
+            BTreeSet::new(),
+            false,
+            &mut cliques,
+        );
+        cliques.sort();
+        cliques
+    }
+
+    /// The largest clique of an undirected graph. If there are several, the lexicographically
+    /// smallest one is returned.
+    #[must_use]
+    pub fn maximum_clique(&self) -> Vec<usize> {
+        let mut best = vec![];
+        self.bron_kerbosch(&mut vec![], self.nodes(), BTreeSet::new(), true, &mut best);
+        best.pop().unwrap_or_default()
+    }
+
+    /// Reports every maximal clique that extends `clique` with nodes of `candidates`.
+    /// Nodes in `excluded` were already tried, so cliques containing them were reported before.
+    /// With `keep_largest`, `cliques` only keeps the largest clique and branches that cannot
+    /// beat it are skipped.
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: BTreeSet<usize>,
+        mut excluded: BTreeSet<usize>,
+        keep_largest: bool,
+        cliques: &mut Vec<Vec<usize>>,
+    ) {
+        assert!(
+            !self.directed,
+            "cliques are only defined for undirected graphs"
+        );
+
+        if candidates.is_empty() && excluded.is_empty() {
+            let mut found = clique.clone();
+            found.sort_unstable();
+
+            if !keep_largest {
+                cliques.push(found);
+            } else if cliques
+                .first()
+                .is_none_or(|best| (found.len(), Reverse(&found)) > (best.len(), Reverse(best)))
+            {
+                *cliques = vec![found];
+            }
+            return;
+        }
+
+        if keep_largest
+            && cliques
+                .first()
+                .is_some_and(|best| clique.len() + candidates.len() < best.len())
+        {
+            return;
+        }
+
+        // the neighbors of the pivot are part of the cliques of the pivot or of another node
+        // that is not a neighbor, so they do not need to be tried on their own.
+        let pivot = candidates
+            .union(&excluded)
+            .copied()
+            .max_by_key(|&node| self.adjacency[node].intersection(&candidates).count())
+            .unwrap();
+        let branches: Vec<usize> = candidates
+            .difference(&self.adjacency[pivot])
+            .copied()
+            .collect();
+
+        for node in branches {
+            let neighbors = &self.adjacency[node];
+
+            clique.push(node);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbors).copied().collect(),
+                excluded.intersection(neighbors).copied().collect(),
+                keep_largest,
+                cliques,
+            );
+            clique.pop();
+
+            candidates.remove(&node);
+            excluded.insert(node);
+        }
+    }
+
+    /// All triangles of an undirected graph as `[a, b, c]` with `a < b < c`, in ascending order.
+    #[must_use]
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        assert!(
+            !self.directed,
+            "triangles are only defined for undirected graphs"
+        );
+
+        let mut triangles = vec![];
+        for (a, neighbors) in self.adjacency.iter().enumerate() {
+            for &b in neighbors.range(a + 1..) {
+                for &c in self.adjacency[b].range(b + 1..) {
+                    if neighbors.contains(&c) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+        triangles
+    }
+
+    /// The connected components of the graph, each in ascending order and ordered by their
+    /// smallest node. Edges of a directed graph are followed in both directions.
+    #[must_use]
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut roots: Vec<usize> = (0..self.len()).collect();
+
+        fn find(roots: &mut [usize], node: usize) -> usize {
+            let mut root = node;
+            while roots[root] != root {
+                root = roots[root];
+            }
+            // compress the path so later lookups are fast.
+            let mut node = node;
+            while roots[node] != root {
+                node = std::mem::replace(&mut roots[node], root);
+            }
+            root
+        }
+
+        for (a, b) in self.edges() {
+            let (a, b) = (find(&mut roots, a), find(&mut roots, b));
+            roots[a.max(b)] = a.min(b);
+        }
+
+        let mut components: Vec<Vec<usize>> = vec![];
+        let mut index: HashMap<usize, usize> = HashMap::new();
+
+        for node in 0..self.len() {
+            let root = find(&mut roots, node);
+            let i = *index.entry(root).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[i].push(node);
+        }
+
+        components
+    }
+
+    /* ------------------------------- directed ------------------------------- */
+
+    /// Orders the nodes of a directed graph so that every edge points forward (Kahn's algorithm).
+    /// Of all valid orders, the lexicographically smallest one is returned.
+    /// Fails with a cycle if there is no such order.
+    pub fn toposort(&self) -> Result<Vec<usize>, Vec<usize>> {
+        assert!(
+            self.directed,
+            "topological orders are only defined for directed graphs"
+        );
+
+        let mut incoming = vec![0; self.len()];
+        for (_, b) in self.edges() {
+            incoming[b] += 1;
+        }
+
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..self.len())
+            .filter(|&node| incoming[node] == 0)
+            .map(Reverse)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(Reverse(node)) = ready.pop() {
+            order.push(node);
+            for next in self.neighbors(node) {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push(Reverse(next));
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            Err(self
+                .find_cycle()
+                .expect("a graph without order has a cycle"))
+        }
+    }
+
+    /// Finds a cycle and returns its nodes in the order they are visited, e.g. `[a, b, c]` for
+    /// the edges `a -> b -> c -> a`. Works for directed and undirected graphs.
+    #[must_use]
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            Active,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.len()];
+        let mut parent = vec![None; self.len()];
+
+        for start in 0..self.len() {
+            if state[start] != State::New {
+                continue;
+            }
+
+            state[start] = State::Active;
+            let mut stack: Vec<(usize, btree_set::Iter<usize>)> =
+                vec![(start, self.adjacency[start].iter())];
+
+            while let Some((node, neighbors)) = stack.last_mut() {
+                let node = *node;
+                let Some(&next) = neighbors.next() else {
+                    state[node] = State::Done;
+                    stack.pop();
+                    continue;
+                };
+
+                // an undirected edge leads back to where it came from.
+                if !self.directed && parent[node] == Some(next) {
+                    continue;
+                }
+
+                match state[next] {
+                    State::New => {
+                        state[next] = State::Active;
+                        parent[next] = Some(node);
+                        stack.push((next, self.adjacency[next].iter()));
+                    }
+                    State::Active => {
+                        let cycle = stack
+                            .iter()
+                            .map(|(node, _)| *node)
+                            .skip_while(|&node| node != next)
+                            .collect();
+                        return Some(cycle);
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+
+        None
+    }
+
+    fn nodes(&self) -> BTreeSet<usize> {
+        (0..self.len()).collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Graph;
+
+    /// Two triangles that share the edge 1-2, a dangling node 4 and a separate edge 5-6.
+    fn example() -> Graph {
+        let mut graph = Graph::undirected(7);
+        for (a, b) in [(0, 1), (0, 2), (1, 2), (1, 3), (2, 3), (3, 4), (5, 6)] {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+
+    #[test]
+    fn builds_graphs_from_labels() {
+        let (graph, labels) =
+            Graph::from_labeled_edges([("kh", "tc"), ("qp", "kh"), ("tc", "qp")], false);
+        assert_eq!(labels, ["kh", "tc", "qp"]);
+        assert_eq!(graph.edges().collect::<Vec<_>>(), [(0, 1), (0, 2), (1, 2)]);
+        assert!(graph.has_edge(2, 0));
+    }
+
+    #[test]
+    fn finds_cliques() {
+        let graph = example();
+        assert_eq!(
+            graph.maximal_cliques(),
+            [vec![0, 1, 2], vec![1, 2, 3], vec![3, 4], vec![5, 6]]
+        );
+        assert_eq!(graph.maximum_clique(), [0, 1, 2]);
+
+        let mut complete = Graph::undirected(5);
+        for a in 0..5 {
+            for b in a + 1..5 {
+                complete.add_edge(a, b);
+            }
+        }
+        assert_eq!(complete.maximum_clique(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn finds_triangles() {
+        assert_eq!(example().triangles(), [[0, 1, 2], [1, 2, 3]]);
+    }
+
+    #[test]
+    fn finds_components() {
+        let mut graph = example();
+        graph.add_edge(7, 7);
+        assert_eq!(
+            graph.components(),
+            [vec![0, 1, 2, 3, 4], vec![5, 6], vec![7]]
+        );
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let mut graph = Graph::directed(5);
+        for (a, b) in [(3, 1), (1, 0), (4, 0), (2, 4)] {
+            graph.add_edge(a, b);
+        }
+        assert_eq!(graph.toposort(), Ok(vec![2, 3, 1, 4, 0]));
+
+        graph.add_edge(0, 2);
+        assert_eq!(graph.toposort(), Err(vec![0, 2, 4]));
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let mut directed = Graph::directed(3);
+        directed.add_edge(0, 1);
+        directed.add_edge(1, 2);
+        directed.add_edge(0, 2);
+        assert_eq!(directed.find_cycle(), None);
+
+        directed.add_edge(2, 0);
+        assert_eq!(directed.find_cycle(), Some(vec![0, 1, 2]));
+
+        let mut tree = Graph::undirected(4);
+        for (a, b) in [(0, 1), (1, 2), (1, 3)] {
+            tree.add_edge(a, b);
+        }
+        assert_eq!(tree.find_cycle(), None);
+        assert_eq!(example().find_cycle(), Some(vec![0, 1, 2]));
+    }
+}
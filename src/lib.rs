@@ -1,3 +1,9 @@
+pub mod bitset;
+pub mod graph;
+pub mod linalg;
+pub mod netlist;
+pub mod property;
 pub mod template;
+pub mod three_bit;
 
 // Use this file to add helper functions and additional modules.
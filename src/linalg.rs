@@ -0,0 +1,198 @@
+/// Dense linear algebra on square, row-major `f32` matrices.
+///
+/// By default, the operations are implemented in pure Rust: the inner loops are written so the
+/// compiler can vectorize them and rows are computed in parallel with rayon. With the `blas`
+/// feature, they call into the system BLAS instead (Accelerate on macOS, `libblas` elsewhere).
+/// Both backends share the same safe API.
+use backend::{dot as dot_impl, symm as symm_impl};
+
+/// Computes `c = a * b` for `n` by `n` matrices where `a` is symmetric.
+/// Only the upper triangle of `a` is read, i.e. `a[i * n + k]` with `i <= k`.
+/// Panics if a matrix does not have `n * n` elements.
+pub fn symm(n: usize, a: &[f32], b: &[f32], c: &mut [f32]) {
+    assert_eq!(a.len(), n * n, "`a` is not a {n}x{n} matrix");
+    assert_eq!(b.len(), n * n, "`b` is not a {n}x{n} matrix");
+    assert_eq!(c.len(), n * n, "`c` is not a {n}x{n} matrix");
+    symm_impl(n, a, b, c);
+}
+
+/// Computes the dot product of two vectors, e.g. the sum of the element-wise product of two
+/// matrices. Panics if the vectors differ in length.
+#[must_use]
+pub fn dot(x: &[f32], y: &[f32]) -> f32 {
+    assert_eq!(x.len(), y.len(), "vectors differ in length");
+    dot_impl(x, y)
+}
+
+/// The number of triangles in an undirected graph, given as a symmetric adjacency matrix
+/// with `n` by `n` elements. This is `trace(a^3) / 6`, computed as `dot(a * a, a) / 6`.
+#[must_use]
+pub fn triangles(n: usize, adjacency: &[f32]) -> usize {
+    let mut square = vec![0.0; n * n];
+    symm(n, adjacency, adjacency, &mut square);
+    let sum = dot(&square, adjacency).round() as usize;
+    debug_assert_eq!(sum % 6, 0);
+    sum / 6
+}
+
+#[cfg(not(feature = "blas"))]
+mod backend {
+    use rayon::prelude::*;
+
+    /// The number of independent accumulators of `dot`, so the additions can be vectorized.
+    const LANES: usize = 8;
+
+    pub fn symm(n: usize, a: &[f32], b: &[f32], c: &mut [f32]) {
+        if n == 0 {
+            return;
+        }
+
+        c.par_chunks_mut(n).enumerate().for_each(|(i, row)| {
+            row.fill(0.0);
+
+            for k in 0..n {
+                // only the upper triangle of `a` is read.
+                let a_ik = if i <= k { a[i * n + k] } else { a[k * n + i] };
+
+                // sparse matrices like adjacency matrices are mostly zeros.
+                if a_ik == 0.0 {
+                    continue;
+                }
+
+                for (c_ij, b_kj) in row.iter_mut().zip(&b[k * n..(k + 1) * n]) {
+                    *c_ij += a_ik * b_kj;
+                }
+            }
+        });
+    }
+
+    pub fn dot(x: &[f32], y: &[f32]) -> f32 {
+        let mut sums = [0.0; LANES];
+
+        let (x_chunks, y_chunks) = (x.chunks_exact(LANES), y.chunks_exact(LANES));
+        let remainder: f32 = (x_chunks.remainder().iter())
+            .zip(y_chunks.remainder())
+            .map(|(x, y)| x * y)
+            .sum();
+
+        for (x, y) in x_chunks.zip(y_chunks) {
+            for lane in 0..LANES {
+                sums[lane] += x[lane] * y[lane];
+            }
+        }
+
+        sums.iter().sum::<f32>() + remainder
+    }
+}
+
+#[cfg(feature = "blas")]
+mod backend {
+    use std::ffi::{c_char, c_float, c_int};
+
+    // Accelerate returns `double` from `sdot_` instead of `float`.
+    // See https://stackoverflow.com/a/77017238
+    #[cfg(target_os = "macos")]
+    type SdotReturn = std::ffi::c_double;
+    #[cfg(not(target_os = "macos"))]
+    type SdotReturn = c_float;
+
+    #[cfg_attr(target_os = "macos", link(name = "Accelerate", kind = "framework"))]
+    #[cfg_attr(not(target_os = "macos"), link(name = "blas"))]
+    extern "C" {
+        fn ssymm_(
+            side: *const c_char,
+            uplo: *const c_char,
+            m: *const c_int,
+            n: *const c_int,
+            alpha: *const c_float,
+            a: *const c_float,
+            lda: *const c_int,
+            b: *const c_float,
+            ldb: *const c_int,
+            beta: *const c_float,
+            c: *mut c_float,
+            ldc: *const c_int,
+        );
+
+        fn sdot_(
+            n: *const c_int,
+            x: *const c_float,
+            incx: *const c_int,
+            y: *const c_float,
+            incy: *const c_int,
+        ) -> SdotReturn;
+    }
+
+    pub fn symm(n: usize, a: &[f32], b: &[f32], c: &mut [f32]) {
+        let n = c_int::try_from(n).expect("matrix is too large for BLAS");
+
+        // BLAS expects column-major matrices, i.e. it sees the transpose of our row-major ones.
+        // `c^T = b^T * a` for a symmetric `a`, so `a` is multiplied from the right and its upper
+        // triangle is the lower triangle in column-major order.
+        //
+        // SAFETY: all matrices have `n * n` elements, which `super::symm` asserts.
+        unsafe {
+            ssymm_(
+                &(b'R' as c_char),
+                &(b'L' as c_char),
+                &n,
+                &n,
+                &1.0,
+                a.as_ptr(),
+                &n,
+                b.as_ptr(),
+                &n,
+                &0.0,
+                c.as_mut_ptr(),
+                &n,
+            );
+        }
+    }
+
+    pub fn dot(x: &[f32], y: &[f32]) -> f32 {
+        let n = c_int::try_from(x.len()).expect("vector is too large for BLAS");
+
+        // SAFETY: both vectors have `n` elements, which `super::dot` asserts.
+        unsafe { sdot_(&n, x.as_ptr(), &1, y.as_ptr(), &1) as f32 }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{dot, symm, triangles};
+
+    #[test]
+    fn multiplies_symmetric_matrices() {
+        // only the upper triangle of `a` is read, the lower one is garbage.
+        let a = [1.0, 2.0, 3.0, 9.0, 4.0, 5.0, 9.0, 9.0, 6.0];
+        let b = [1.0, 0.0, 2.0, 0.0, 1.0, 0.0, 3.0, 0.0, 1.0];
+        let mut c = [0.0; 9];
+
+        symm(3, &a, &b, &mut c);
+
+        // a is [[1, 2, 3], [2, 4, 5], [3, 5, 6]].
+        assert_eq!(c, [10.0, 2.0, 5.0, 17.0, 4.0, 9.0, 21.0, 5.0, 12.0]);
+    }
+
+    #[test]
+    fn computes_dot_products() {
+        let x: Vec<f32> = (1..=19).map(|x| x as f32).collect();
+        assert_eq!(dot(&x, &x), 2470.0);
+        assert_eq!(dot(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn counts_triangles() {
+        // two triangles that share the edge 1-2, and a dangling node 4.
+        let edges = [(0, 1), (0, 2), (1, 2), (1, 3), (2, 3), (3, 4)];
+        let mut adjacency = [0.0; 25];
+        for (i, j) in edges {
+            adjacency[i * 5 + j] = 1.0;
+            adjacency[j * 5 + i] = 1.0;
+        }
+
+        assert_eq!(triangles(5, &adjacency), 2);
+    }
+}
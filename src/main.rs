@@ -1,13 +1,19 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, profile_check, read, scaffold, solve, status, time, watch,
+};
 use args::{parse, AppArguments};
 
+#[cfg(feature = "tui")]
+use advent_of_code::template::commands::tui;
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::{commands::countdown, Day};
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        config::{self, BenchOptions},
+        input::Input,
+        Day,
+    };
     use std::process;
 
     pub enum AppArguments {
@@ -17,44 +23,97 @@ mod args {
         Read {
             day: Day,
         },
+        Examples {
+            day: Day,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: Input,
+            all_inputs: bool,
+            time: bool,
+            bench: BenchOptions,
         },
         All {
             release: bool,
+            input: Input,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            input: Input,
+            all_inputs: bool,
+            bench: BenchOptions,
+        },
+        Status,
+        ProfileCheck {
+            day: Option<Day>,
+            input: Input,
+        },
+        #[cfg(feature = "tui")]
+        Tui,
+        WatchDay {
+            day: Day,
+            release: bool,
+            input: Input,
         },
         #[cfg(feature = "today")]
         Today,
+        #[cfg(feature = "today")]
+        Countdown,
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let (app_args, year) = parse_from(pico_args::Arguments::from_env())?;
+        config::init(year);
+        Ok(app_args)
+    }
 
-        let app_args = match args.subcommand()?.as_deref() {
+    /// Parses the command and the global `--year` flag that overrides `aoc.toml`.
+    fn parse_from(
+        mut args: pico_args::Arguments,
+    ) -> Result<(AppArguments, Option<u16>), Box<dyn std::error::Error>> {
+        let subcommand = args.subcommand()?;
+
+        // read before the free arguments, so it is not taken for the day.
+        let year = args.opt_value_from_str("--year")?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                input: parse_input(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let input = parse_input(&mut args)?;
+                let all_inputs = args.contains("--all-inputs");
+                let bench = parse_bench(&mut args)?;
+                let day = args.opt_free_from_str()?;
+
+                if all_inputs && day.is_none() {
+                    return Err(
+                        "`--all-inputs` requires a day, e.g. `cargo time 01 --all-inputs`".into(),
+                    );
+                }
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    day,
                     store,
+                    input,
+                    all_inputs,
+                    bench,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -63,19 +122,46 @@ mod args {
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("examples") => AppArguments::Examples {
+                day: args.free_from_str()?,
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                input: parse_input(&mut args)?,
+                all_inputs: args.contains("--all-inputs"),
+                time: args.contains("--time"),
+                bench: parse_bench(&mut args)?,
+            },
+            Some("status") => AppArguments::Status,
+            Some("profile-check") => {
+                let input = parse_input(&mut args)?;
+
+                AppArguments::ProfileCheck {
+                    day: args.opt_free_from_str()?,
+                    input,
+                }
+            }
+            #[cfg(feature = "tui")]
+            Some("tui") => AppArguments::Tui,
+            Some("watch-day") => AppArguments::WatchDay {
+                day: args.free_from_str()?,
+                release: args.contains("--release"),
+                input: parse_input(&mut args)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
+            #[cfg(feature = "today")]
+            Some("countdown") => AppArguments::Countdown,
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -91,7 +177,49 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((app_args, year))
+    }
+
+    fn parse_bench(args: &mut pico_args::Arguments) -> Result<BenchOptions, pico_args::Error> {
+        Ok(BenchOptions {
+            time: args.opt_value_from_fn("--bench-time", config::parse_duration)?,
+            min_samples: args.opt_value_from_fn("--min-samples", config::parse_samples)?,
+            max_samples: args.opt_value_from_fn("--max-samples", config::parse_samples)?,
+            warmup: args.opt_value_from_str("--warmup")?,
+        })
+    }
+
+    fn parse_input(args: &mut pico_args::Arguments) -> Result<Input, pico_args::Error> {
+        Ok(args
+            .opt_value_from_str("--input")?
+            .unwrap_or(Input::Default))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{parse_from, AppArguments};
+
+        fn parse(args: &[&str]) -> (AppArguments, Option<u16>) {
+            let args = args.iter().map(Into::into).collect();
+            parse_from(pico_args::Arguments::from_vec(args)).unwrap()
+        }
+
+        #[test]
+        fn reads_the_year_before_the_day() {
+            let (args, year) = parse(&["solve", "--year", "2023", "01"]);
+            assert_eq!(year, Some(2023));
+            assert!(matches!(args, AppArguments::Solve { day, .. } if day.into_inner() == 1));
+
+            let (args, year) = parse(&["time", "--year", "2023"]);
+            assert_eq!(year, Some(2023));
+            assert!(matches!(args, AppArguments::Time { day: None, .. }));
+
+            let (args, year) = parse(&["profile-check", "--year", "2023", "05"]);
+            assert_eq!(year, Some(2023));
+            assert!(
+                matches!(args, AppArguments::ProfileCheck { day: Some(day), .. } if day.into_inner() == 5)
+            );
+        }
     }
 }
 
@@ -102,43 +230,81 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All { release, input } => all::handle(release, &input),
+            AppArguments::Time {
+                day: Some(day),
+                all_inputs: true,
+                bench,
+                ..
+            } => time::handle_all_inputs(day, &bench),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                input,
+                bench,
+                ..
+            } => time::handle(day, all, store, &input, &bench),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
+            AppArguments::Examples { day } => examples::handle(day),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                template,
+                answer_type,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(day, overwrite, template.as_deref(), answer_type.as_deref());
                 if download {
                     download::handle(day);
+                    examples::handle(day);
                 }
             }
+            AppArguments::Solve {
+                day,
+                release,
+                all_inputs: true,
+                time,
+                bench,
+                ..
+            } => solve::handle_all_inputs(day, release, time.then_some(&bench)),
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                time,
+                bench,
+                ..
+            } => solve::handle(day, release, dhat, submit, &input, time.then_some(&bench)),
+            AppArguments::Status => status::handle(),
+            AppArguments::ProfileCheck { day, input } => profile_check::handle(day, &input),
+            #[cfg(feature = "tui")]
+            AppArguments::Tui => tui::handle(),
+            AppArguments::WatchDay {
+                day,
+                release,
+                input,
+            } => watch::handle(day, release, &input),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
+                        scaffold::handle(day, false, None, None);
                         download::handle(day);
+                        examples::handle(day);
                         read::handle(day)
                     }
                     None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
+                        println!("No puzzle unlocks today, waiting for the next one.");
+                        countdown::handle();
                     }
                 };
             }
+            #[cfg(feature = "today")]
+            AppArguments::Countdown => countdown::handle(),
         },
     };
 }
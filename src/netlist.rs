@@ -0,0 +1,903 @@
+/// Circuits of logic gates, like the one of day 24.
+///
+/// A [`Netlist`] connects named wires with `AND`, `OR` and `XOR` gates. Wires named like `x00`,
+/// `x01`, ... form a bus that holds a number, with `x00` as the least significant bit. The
+/// circuit can be evaluated for its initial values or arbitrary `x` and `y` numbers, exported
+/// to Graphviz DOT or Verilog, and checked against an adder of its `x` and `y` buses.
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::{self, Display, Write},
+};
+
+use crate::graph::Graph;
+use crate::property::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GateKind {
+    And,
+    Or,
+    Xor,
+}
+
+impl GateKind {
+    #[must_use]
+    pub fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            GateKind::And => a & b,
+            GateKind::Or => a | b,
+            GateKind::Xor => a ^ b,
+        }
+    }
+
+    /// The operator of the gate in Verilog.
+    fn operator(self) -> char {
+        match self {
+            GateKind::And => '&',
+            GateKind::Or => '|',
+            GateKind::Xor => '^',
+        }
+    }
+}
+
+impl Display for GateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GateKind::And => "AND",
+            GateKind::Or => "OR",
+            GateKind::Xor => "XOR",
+        })
+    }
+}
+
+/// A gate that drives the `output` wire. Wires are referred to by their index, see
+/// [`Netlist::wire`] and [`Netlist::name`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Gate {
+    pub kind: GateKind,
+    pub inputs: [usize; 2],
+    pub output: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Parser(String),
+    /// The gates form a loop, so the circuit cannot be evaluated. Contains the wires of the loop.
+    Cycle(Vec<String>),
+    /// A gate reads a wire that has no value and is not driven by a gate.
+    Undriven(String),
+    /// The circuit does not have the buses of an adder.
+    NotAnAdder(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse circuit: {e}"),
+            Error::Cycle(wires) => write!(f, "the wires {} form a cycle", wires.join(" -> ")),
+            Error::Undriven(wire) => write!(f, "the wire {wire} has no value"),
+            Error::NotAnAdder(e) => write!(f, "not an adder: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Clone, Debug, Default)]
+pub struct Netlist {
+    names: Vec<String>,
+    wires: HashMap<String, usize>,
+    gates: Vec<Gate>,
+    /// The gate that drives each wire, if any.
+    drivers: Vec<Option<usize>>,
+    initial: Vec<Option<bool>>,
+}
+
+impl Netlist {
+    /// Parses the puzzle input, i.e. initial values like `x00: 1`, followed by gates like
+    /// `x00 AND y00 -> z00`.
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut netlist = Self::default();
+
+        for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some((wire, value)) = line.split_once(": ") {
+                let value = match value {
+                    "0" => false,
+                    "1" => true,
+                    _ => return Err(Error::Parser(format!("invalid value `{value}`"))),
+                };
+                let wire = netlist.add_wire(wire);
+                netlist.initial[wire] = Some(value);
+                continue;
+            }
+
+            let invalid = || Error::Parser(format!("invalid gate `{line}`"));
+            let (inputs, output) = line.split_once(" -> ").ok_or_else(invalid)?;
+            let [a, kind, b] = inputs.split(' ').collect::<Vec<_>>()[..] else {
+                return Err(invalid());
+            };
+            let kind = match kind {
+                "AND" => GateKind::And,
+                "OR" => GateKind::Or,
+                "XOR" => GateKind::Xor,
+                _ => return Err(Error::Parser(format!("unknown gate `{kind}`"))),
+            };
+
+            netlist.add_gate(kind, a, b, output)?;
+        }
+
+        Ok(netlist)
+    }
+
+    fn add_wire(&mut self, name: &str) -> usize {
+        if let Some(&wire) = self.wires.get(name) {
+            return wire;
+        }
+
+        self.names.push(name.to_string());
+        self.drivers.push(None);
+        self.initial.push(None);
+        self.wires.insert(name.to_string(), self.names.len() - 1);
+        self.names.len() - 1
+    }
+
+    /// Adds a gate. Fails if the output wire is already driven by another gate.
+    pub fn add_gate(
+        &mut self,
+        kind: GateKind,
+        a: &str,
+        b: &str,
+        output: &str,
+    ) -> Result<(), Error> {
+        let inputs = [self.add_wire(a), self.add_wire(b)];
+        let output = self.add_wire(output);
+
+        if self.drivers[output].is_some() {
+            return Err(Error::Parser(format!(
+                "{} is driven twice",
+                self.names[output]
+            )));
+        }
+
+        self.drivers[output] = Some(self.gates.len());
+        self.gates.push(Gate {
+            kind,
+            inputs,
+            output,
+        });
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn wire(&self, name: &str) -> Option<usize> {
+        self.wires.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn name(&self, wire: usize) -> &str {
+        &self.names[wire]
+    }
+
+    #[must_use]
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    /// The gate that drives a wire, or `None` for inputs.
+    #[must_use]
+    pub fn driver(&self, wire: usize) -> Option<&Gate> {
+        self.drivers[wire].map(|gate| &self.gates[gate])
+    }
+
+    /// The wires of a bus like `z00`, `z01`, ..., ordered from the least significant bit.
+    #[must_use]
+    pub fn bus(&self, prefix: char) -> Vec<usize> {
+        let mut bus: Vec<(usize, usize)> = self
+            .names
+            .iter()
+            .enumerate()
+            .filter_map(|(wire, name)| {
+                let bit = name.strip_prefix(prefix)?.parse().ok()?;
+                Some((bit, wire))
+            })
+            .collect();
+
+        bus.sort_unstable();
+        bus.into_iter().map(|(_, wire)| wire).collect()
+    }
+
+    /// The wires in an order in which every wire comes after the inputs of its gate.
+    pub fn order(&self) -> Result<Vec<usize>, Error> {
+        let mut graph = Graph::directed(self.names.len());
+        for gate in &self.gates {
+            for input in gate.inputs {
+                graph.add_edge(input, gate.output);
+            }
+        }
+
+        graph.toposort().map_err(|cycle| {
+            Error::Cycle(cycle.into_iter().map(|w| self.names[w].clone()).collect())
+        })
+    }
+
+    /// Evaluates the circuit for the initial values of its wires.
+    pub fn evaluate(&self) -> Result<Values<'_>, Error> {
+        self.evaluate_in(&self.order()?, |wire| self.initial[wire])
+    }
+
+    /// Evaluates the circuit with the `x` and `y` buses set to the given numbers. Other inputs
+    /// keep their initial values.
+    pub fn evaluate_with(&self, x: u64, y: u64) -> Result<Values<'_>, Error> {
+        let (x_bus, y_bus) = (self.bus('x'), self.bus('y'));
+        self.evaluate_in(&self.order()?, |wire| {
+            bus_bit(&x_bus, x, wire)
+                .or_else(|| bus_bit(&y_bus, y, wire))
+                .or(self.initial[wire])
+        })
+    }
+
+    /// Evaluates the wires in `order`, taking the values of inputs from `input`.
+    fn evaluate_in(
+        &self,
+        order: &[usize],
+        input: impl Fn(usize) -> Option<bool>,
+    ) -> Result<Values<'_>, Error> {
+        let mut values = vec![None; self.names.len()];
+
+        for &wire in order {
+            values[wire] = match self.driver(wire) {
+                Some(gate) => {
+                    let [a, b] = gate.inputs.map(|input| {
+                        values[input].ok_or_else(|| Error::Undriven(self.names[input].clone()))
+                    });
+                    Some(gate.kind.apply(a?, b?))
+                }
+                None => input(wire),
+            };
+        }
+
+        Ok(Values {
+            netlist: self,
+            values,
+        })
+    }
+
+    /// The circuit in the Graphviz DOT format. Gates are drawn as nodes between their wires.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+
+        for (wire, name) in self.names.iter().enumerate() {
+            let shape = match self.drivers[wire] {
+                None => "box",
+                Some(_) if name.starts_with('z') => "doublecircle",
+                Some(_) => "ellipse",
+            };
+            writeln!(dot, "    \"{name}\" [shape={shape}];").unwrap();
+        }
+
+        for (i, gate) in self.gates.iter().enumerate() {
+            let [a, b] = gate.inputs.map(|wire| &self.names[wire]);
+            let output = &self.names[gate.output];
+            writeln!(
+                dot,
+                "    gate{i} [label=\"{}\", shape=diamond];\n    \"{a}\" -> gate{i};\n    \"{b}\" -> gate{i};\n    gate{i} -> \"{output}\";",
+                gate.kind
+            )
+            .unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The circuit as a Verilog module. Wires without a gate are inputs and the `z` bus is the
+    /// output of the module.
+    #[must_use]
+    pub fn to_verilog(&self, module: &str) -> String {
+        let mut inputs = vec![];
+        let mut outputs = vec![];
+        let mut internal = vec![];
+
+        for (wire, name) in self.names.iter().enumerate() {
+            match self.drivers[wire] {
+                None => inputs.push(name.as_str()),
+                Some(_) if name.starts_with('z') => outputs.push(name.as_str()),
+                Some(_) => internal.push(name.as_str()),
+            }
+        }
+
+        inputs.sort_unstable();
+        outputs.sort_unstable();
+        internal.sort_unstable();
+
+        let ports = inputs
+            .iter()
+            .map(|name| format!("    input {name}"))
+            .chain(outputs.iter().map(|name| format!("    output {name}")))
+            .collect::<Vec<_>>()
+            .join(",\n");
+
+        let mut verilog = format!("module {module} (\n{ports}\n);\n");
+        for name in internal {
+            writeln!(verilog, "    wire {name};").unwrap();
+        }
+        for gate in &self.gates {
+            let [a, b] = gate.inputs.map(|wire| &self.names[wire]);
+            writeln!(
+                verilog,
+                "    assign {} = {a} {} {b};",
+                self.names[gate.output],
+                gate.kind.operator()
+            )
+            .unwrap();
+        }
+
+        verilog.push_str("endmodule\n");
+        verilog
+    }
+
+    /// Checks whether the circuit adds the numbers on its `x` and `y` buses into its `z` bus.
+    ///
+    /// The circuit is evaluated for edge cases, i.e. zeros, all ones and every single bit with
+    /// and without a carry, followed by `random_cases` random inputs.
+    pub fn verify_adder(&self, rng: &mut Rng, random_cases: usize) -> Result<AdderReport, Error> {
+        let buses = self.adder_buses()?;
+        let order = self.order()?;
+        let mut report = AdderReport {
+            width: buses.width(),
+            wrong_bits: 0,
+            failures: vec![],
+        };
+
+        for (x, y) in buses.cases(rng, random_cases) {
+            let actual = self.add(&order, &buses, x, y)?;
+            let expected = x + y;
+
+            if actual != expected {
+                report.wrong_bits |= actual ^ expected;
+                report.failures.push(AdderFailure {
+                    x,
+                    y,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn adder_buses(&self) -> Result<AdderBuses, Error> {
+        let buses = AdderBuses {
+            x: self.bus('x'),
+            y: self.bus('y'),
+            z: self.bus('z'),
+        };
+        let width = buses.width();
+
+        if width == 0 || width > 63 || buses.y.len() != width || buses.z.len() != width + 1 {
+            return Err(Error::NotAnAdder(format!(
+                "expected n x and y bits and n + 1 z bits, got {}, {} and {}",
+                width,
+                buses.y.len(),
+                buses.z.len()
+            )));
+        }
+
+        Ok(buses)
+    }
+
+    /// Evaluates the circuit for `x + y` and returns the number on the `z` bus.
+    fn add(&self, order: &[usize], buses: &AdderBuses, x: u64, y: u64) -> Result<u64, Error> {
+        let mut values = self.initial.clone();
+        for (bit, (&x_wire, &y_wire)) in buses.x.iter().zip(&buses.y).enumerate() {
+            values[x_wire] = Some(x >> bit & 1 == 1);
+            values[y_wire] = Some(y >> bit & 1 == 1);
+        }
+
+        for &wire in order {
+            if let Some(gate) = self.driver(wire) {
+                let [a, b] = gate.inputs.map(|input| {
+                    values[input].ok_or_else(|| Error::Undriven(self.names[input].clone()))
+                });
+                values[wire] = Some(gate.kind.apply(a?, b?));
+            }
+        }
+
+        buses.z.iter().enumerate().try_fold(0, |z, (bit, &wire)| {
+            let value = values[wire].ok_or_else(|| Error::Undriven(self.names[wire].clone()))?;
+            Ok(z | u64::from(value) << bit)
+        })
+    }
+
+    /// Swaps the output wires of the gates that drive `a` and `b`.
+    /// Panics if one of the wires is not driven by a gate.
+    pub fn swap_outputs(&mut self, a: usize, b: usize) {
+        let (gate_a, gate_b) = (self.drivers[a].unwrap(), self.drivers[b].unwrap());
+        self.gates[gate_a].output = b;
+        self.gates[gate_b].output = a;
+        self.drivers.swap(a, b);
+    }
+
+    /// Finds at most `max_swaps` pairs of gate outputs whose swap turns the circuit into a
+    /// correct adder, see [`Netlist::verify_adder`]. Returns `None` if there is no such repair.
+    ///
+    /// Wires that break the structure of a ripple-carry adder are paired up first. If that does
+    /// not lead to a correct adder, e.g. because the circuit is built differently, the lowest
+    /// wrong output bit is fixed one swap at a time instead.
+    pub fn repair_adder(&self, max_swaps: usize) -> Result<Option<Vec<Swap>>, Error> {
+        let buses = self.adder_buses()?;
+        let mut netlist = self.clone();
+
+        let suspects = self.structural_suspects(&buses);
+        if suspects.len() <= 2 * max_swaps && suspects.len().is_multiple_of(2) {
+            let wires: Vec<usize> = suspects.keys().copied().collect();
+            let mut pairs = vec![];
+
+            if netlist.pair_suspects(&buses, &wires, &mut pairs) {
+                let swaps = pairs
+                    .into_iter()
+                    .map(|(a, b)| Swap {
+                        wires: [self.names[a].clone(), self.names[b].clone()],
+                        reason: format!("{}, and {}", suspects[&a], suspects[&b]),
+                    })
+                    .collect();
+                return Ok(Some(swaps));
+            }
+        }
+
+        let mut swaps = vec![];
+        if netlist.search_repair(&buses, max_swaps, &mut swaps) {
+            return Ok(Some(swaps));
+        }
+
+        Ok(None)
+    }
+
+    /// Checks the gates against the rules of a ripple-carry adder and returns the output wires
+    /// that break them, along with the broken rule.
+    fn structural_suspects(&self, buses: &AdderBuses) -> BTreeMap<usize, String> {
+        let mut readers: Vec<Vec<GateKind>> = vec![vec![]; self.names.len()];
+        for gate in &self.gates {
+            for input in gate.inputs {
+                readers[input].push(gate.kind);
+            }
+        }
+
+        let is_input = |wire: usize| self.drivers[wire].is_none();
+        let first_bit = [buses.x[0], buses.y[0]];
+        let last_z = buses.z[buses.width()];
+        let mut suspects = BTreeMap::new();
+
+        for gate in &self.gates {
+            let output = gate.output;
+            let name = &self.names[output];
+            let reads_inputs = gate.inputs.iter().all(|&wire| is_input(wire));
+            let reads_first_bit = gate.inputs.iter().all(|wire| first_bit.contains(wire));
+            let reads = |kind: GateKind| readers[output].contains(&kind);
+
+            let broken = match gate.kind {
+                _ if output == last_z && gate.kind != GateKind::Or && buses.width() > 1 => {
+                    Some(format!("the carry out {name} must be an OR gate"))
+                }
+                GateKind::And | GateKind::Or if buses.z.contains(&output) && output != last_z => {
+                    Some(format!("the output bit {name} must be a XOR gate"))
+                }
+                GateKind::Xor if !reads_inputs && !buses.z.contains(&output) => Some(format!(
+                    "the XOR gate of {name} adds a carry, so it must be an output bit"
+                )),
+                GateKind::Xor if reads_inputs && !reads_first_bit && !reads(GateKind::Xor) => {
+                    Some(format!("the half sum {name} must be added to a carry"))
+                }
+                GateKind::And if !reads_first_bit && !reads(GateKind::Or) => Some(format!(
+                    "the partial carry {name} must be combined by an OR gate"
+                )),
+                GateKind::Or if !reads(GateKind::Xor) && output != last_z => {
+                    Some(format!("the carry {name} must be added to the next bit"))
+                }
+                _ => None,
+            };
+
+            if let Some(reason) = broken {
+                suspects.insert(output, reason);
+            }
+        }
+
+        suspects
+    }
+
+    /// Tries every way to pair up `wires` and keeps the first pairing that results in a correct
+    /// adder in `pairs`.
+    fn pair_suspects(
+        &mut self,
+        buses: &AdderBuses,
+        wires: &[usize],
+        pairs: &mut Vec<(usize, usize)>,
+    ) -> bool {
+        let Some((&first, rest)) = wires.split_first() else {
+            return self.is_adder(buses);
+        };
+
+        for i in 0..rest.len() {
+            let partner = rest[i];
+            let remaining: Vec<usize> = rest[..i].iter().chain(&rest[i + 1..]).copied().collect();
+
+            self.swap_outputs(first, partner);
+            pairs.push((first, partner));
+            if self.pair_suspects(buses, &remaining, pairs) {
+                return true;
+            }
+            pairs.pop();
+            self.swap_outputs(first, partner);
+        }
+
+        false
+    }
+
+    /// Fixes the lowest wrong output bit with a swap and continues with the next wrong bit.
+    ///
+    /// A swap has to involve a gate that only the wrong bit and the bit after it depend on,
+    /// because the lower bits are already correct. The other gate can be any gate that the
+    /// lower bits do not depend on.
+    fn search_repair(&mut self, buses: &AdderBuses, budget: usize, swaps: &mut Vec<Swap>) -> bool {
+        let Ok(wrong) = self.lowest_wrong_bit(buses) else {
+            return false;
+        };
+        let Some(bit) = wrong else {
+            return self.is_adder(buses);
+        };
+        if budget == 0 {
+            return false;
+        }
+
+        let settled = self.cone(&buses.z[..bit]);
+        let candidates: Vec<usize> = self
+            .cone(&buses.z[bit..(bit + 2).min(buses.z.len())])
+            .into_iter()
+            .filter(|wire| !settled.contains(wire))
+            .collect();
+        let partners: Vec<usize> = self
+            .gates
+            .iter()
+            .map(|gate| gate.output)
+            .filter(|wire| !settled.contains(wire))
+            .collect();
+
+        for &a in &candidates {
+            for &b in &partners {
+                if a == b {
+                    continue;
+                }
+
+                self.swap_outputs(a, b);
+                let fixed = match self.lowest_wrong_bit(buses) {
+                    Ok(next) => next.is_none_or(|next| next > bit),
+                    // the swap created a cycle.
+                    Err(_) => false,
+                };
+
+                if fixed {
+                    swaps.push(Swap {
+                        wires: [self.names[a].clone(), self.names[b].clone()],
+                        reason: format!(
+                            "the output bit {} is wrong unless they are swapped",
+                            self.names[buses.z[bit]]
+                        ),
+                    });
+                    if self.search_repair(buses, budget - 1, swaps) {
+                        return true;
+                    }
+                    swaps.pop();
+                }
+                self.swap_outputs(a, b);
+            }
+        }
+
+        false
+    }
+
+    /// The gate outputs that the given wires depend on, including the wires themselves.
+    fn cone(&self, wires: &[usize]) -> BTreeSet<usize> {
+        let mut cone = BTreeSet::new();
+        let mut stack: Vec<usize> = wires.to_vec();
+
+        while let Some(wire) = stack.pop() {
+            if let Some(gate) = self.driver(wire) {
+                if cone.insert(wire) {
+                    stack.extend(gate.inputs);
+                }
+            }
+        }
+
+        cone
+    }
+
+    /// The lowest output bit that is wrong for any of the edge cases of the adder.
+    fn lowest_wrong_bit(&self, buses: &AdderBuses) -> Result<Option<usize>, Error> {
+        let order = self.order()?;
+        let mut lowest = None;
+
+        for (x, y) in buses.cases(&mut Rng::new(0), 0) {
+            let wrong = self.add(&order, buses, x, y)? ^ (x + y);
+            if wrong != 0 {
+                let bit = wrong.trailing_zeros() as usize;
+                lowest = Some(lowest.map_or(bit, |lowest: usize| lowest.min(bit)));
+            }
+        }
+
+        Ok(lowest)
+    }
+
+    fn is_adder(&self, buses: &AdderBuses) -> bool {
+        let Ok(order) = self.order() else {
+            return false;
+        };
+
+        buses
+            .cases(&mut Rng::new(REPAIR_SEED), REPAIR_CASES)
+            .into_iter()
+            .all(|(x, y)| self.add(&order, buses, x, y) == Ok(x + y))
+    }
+}
+
+/// The seed and number of random cases that a repaired adder is verified with.
+const REPAIR_SEED: u64 = 24;
+const REPAIR_CASES: usize = 100;
+
+/// The buses of an adder: `z = x + y`, ordered from the least significant bit.
+struct AdderBuses {
+    x: Vec<usize>,
+    y: Vec<usize>,
+    z: Vec<usize>,
+}
+
+impl AdderBuses {
+    fn width(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Zeros, all ones and every single bit with and without a carry, followed by
+    /// `random_cases` random inputs.
+    fn cases(&self, rng: &mut Rng, random_cases: usize) -> Vec<(u64, u64)> {
+        let max = (1 << self.width()) - 1;
+        let mut cases = vec![(0, 0), (max, 0), (0, max), (max, max), (max, 1)];
+        for bit in 0..self.width() {
+            let x = 1 << bit;
+            cases.extend([(x, 0), (0, x), (x, x), (x, max ^ x)]);
+        }
+        cases.extend((0..random_cases).map(|_| (rng.range(0..max + 1), rng.range(0..max + 1))));
+        cases
+    }
+}
+
+/// A swap of two gate outputs that [`Netlist::repair_adder`] found, with the reason why.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Swap {
+    pub wires: [String; 2],
+    pub reason: String,
+}
+
+/// The value of `wire` if it is part of `bus`, taken from the bits of `number`.
+fn bus_bit(bus: &[usize], number: u64, wire: usize) -> Option<bool> {
+    let bit = bus.iter().position(|&w| w == wire)?;
+    Some(number >> bit & 1 == 1)
+}
+
+/// The values of all wires after evaluating a [`Netlist`].
+#[derive(Clone, Debug)]
+pub struct Values<'a> {
+    netlist: &'a Netlist,
+    values: Vec<Option<bool>>,
+}
+
+impl Values<'_> {
+    /// The value of a wire, or `None` if the wire does not exist or has no value.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<bool> {
+        self.values[self.netlist.wire(name)?]
+    }
+
+    /// The number on a bus, e.g. `z` for the output of day 24.
+    pub fn number(&self, prefix: char) -> Result<u64, Error> {
+        self.netlist
+            .bus(prefix)
+            .iter()
+            .enumerate()
+            .try_fold(0, |number, (bit, &wire)| {
+                let value = self.values[wire]
+                    .ok_or_else(|| Error::Undriven(self.netlist.names[wire].clone()))?;
+                Ok(number | u64::from(value) << bit)
+            })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AdderFailure {
+    pub x: u64,
+    pub y: u64,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+/// The result of [`Netlist::verify_adder`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdderReport {
+    /// The number of bits of each summand.
+    pub width: usize,
+    /// A mask of the output bits that were wrong for any input.
+    pub wrong_bits: u64,
+    pub failures: Vec<AdderFailure>,
+}
+
+impl AdderReport {
+    #[must_use]
+    pub fn is_correct(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The indices of the output bits that were wrong for any input, in ascending order.
+    #[must_use]
+    pub fn wrong_bits(&self) -> Vec<usize> {
+        (0..=self.width)
+            .filter(|bit| self.wrong_bits >> bit & 1 == 1)
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Error, Netlist};
+    use crate::property::Rng;
+
+    const EXAMPLE: &str = "x00: 1\nx01: 1\nx02: 1\ny00: 0\ny01: 1\ny02: 0\n\n\
+        x00 AND y00 -> z00\nx01 XOR y01 -> z01\nx02 OR y02 -> z02";
+
+    /// A ripple-carry adder of `width` bits. The outputs of the gates listed in `swaps` are
+    /// swapped in pairs.
+    fn adder(width: usize, swaps: &[(&str, &str)]) -> Netlist {
+        let mut gates = vec![];
+        let mut carry = String::new();
+
+        for bit in 0..width {
+            let (x, y, z) = (
+                format!("x{bit:02}"),
+                format!("y{bit:02}"),
+                format!("z{bit:02}"),
+            );
+            if bit == 0 {
+                gates.push(format!("{x} XOR {y} -> {z}"));
+                gates.push(format!("{x} AND {y} -> c{bit:02}"));
+            } else {
+                gates.push(format!("{x} XOR {y} -> s{bit:02}"));
+                gates.push(format!("{x} AND {y} -> a{bit:02}"));
+                gates.push(format!("s{bit:02} XOR {carry} -> {z}"));
+                gates.push(format!("s{bit:02} AND {carry} -> d{bit:02}"));
+                gates.push(format!("a{bit:02} OR d{bit:02} -> c{bit:02}"));
+            }
+            carry = format!("c{bit:02}");
+        }
+
+        let mut input = gates.join("\n").replace(&carry, &format!("z{width:02}"));
+        for (a, b) in swaps {
+            input = input
+                .replace(&format!("-> {a}"), "-> TMP")
+                .replace(&format!("-> {b}"), &format!("-> {a}"))
+                .replace("-> TMP", &format!("-> {b}"));
+        }
+        Netlist::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn evaluates_circuits() {
+        let netlist = Netlist::parse(EXAMPLE).unwrap();
+        let values = netlist.evaluate().unwrap();
+        assert_eq!(values.get("z01"), Some(false));
+        assert_eq!(values.number('z'), Ok(0b100));
+        assert_eq!(values.number('x'), Ok(0b111));
+
+        let values = netlist.evaluate_with(0b011, 0b001).unwrap();
+        assert_eq!(values.number('z'), Ok(0b011));
+    }
+
+    #[test]
+    fn reports_broken_circuits() {
+        let netlist = Netlist::parse("a AND x00 -> b\nb OR x00 -> a").unwrap();
+        assert!(matches!(netlist.evaluate(), Err(Error::Cycle(_))));
+
+        let netlist = Netlist::parse("x00 AND y00 -> z00").unwrap();
+        assert_eq!(
+            netlist.evaluate().unwrap_err(),
+            Error::Undriven("x00".into())
+        );
+        assert!(Netlist::parse("x00 NAND y00 -> z00").is_err());
+        assert!(Netlist::parse("x00 AND y00 -> z00\nx00 OR y00 -> z00").is_err());
+    }
+
+    #[test]
+    fn exports_circuits() {
+        let netlist = Netlist::parse(EXAMPLE).unwrap();
+
+        let dot = netlist.to_dot();
+        assert!(dot.starts_with("digraph circuit {"));
+        assert!(dot.contains("gate1 [label=\"XOR\", shape=diamond];"));
+        assert!(dot.contains("\"y01\" -> gate1;"));
+        assert!(dot.contains("gate1 -> \"z01\";"));
+
+        let verilog = netlist.to_verilog("example");
+        assert!(verilog.starts_with("module example (\n    input x00,"));
+        assert!(verilog.contains("    output z02\n);"));
+        assert!(verilog.contains("    assign z02 = x02 | y02;"));
+        assert!(verilog.ends_with("endmodule\n"));
+    }
+
+    #[test]
+    fn verifies_adders() {
+        let report = adder(8, &[]).verify_adder(&mut Rng::new(1), 100).unwrap();
+        assert!(report.is_correct());
+        assert_eq!(report.width, 8);
+
+        let report = adder(8, &[("z03", "a03")])
+            .verify_adder(&mut Rng::new(1), 100)
+            .unwrap();
+        assert!(!report.is_correct());
+        assert_eq!(report.wrong_bits()[0], 3);
+
+        let netlist = Netlist::parse(EXAMPLE).unwrap();
+        assert!(matches!(
+            netlist.verify_adder(&mut Rng::new(1), 0),
+            Err(Error::NotAnAdder(_))
+        ));
+    }
+
+    fn swapped_wires(netlist: &Netlist, max_swaps: usize) -> Option<Vec<String>> {
+        let swaps = netlist.repair_adder(max_swaps).unwrap()?;
+        let mut wires: Vec<String> = swaps.into_iter().flat_map(|swap| swap.wires).collect();
+        wires.sort();
+        Some(wires)
+    }
+
+    #[test]
+    fn repairs_adders() {
+        let swaps = [
+            ("z05", "a05"),
+            ("s10", "a10"),
+            ("z15", "d15"),
+            ("z20", "c20"),
+        ];
+        let netlist = adder(24, &swaps);
+        assert!(!netlist
+            .verify_adder(&mut Rng::new(1), 0)
+            .unwrap()
+            .is_correct());
+
+        let repair = netlist.repair_adder(4).unwrap().unwrap();
+        assert_eq!(repair.len(), 4);
+        assert!(repair.iter().all(|swap| !swap.reason.is_empty()));
+        assert_eq!(
+            swapped_wires(&netlist, 4).unwrap(),
+            ["a05", "a10", "c20", "d15", "s10", "z05", "z15", "z20"]
+        );
+
+        assert_eq!(swapped_wires(&adder(8, &[]), 0), Some(vec![]));
+        let netlist = adder(8, &[("z02", "a02"), ("s04", "a04"), ("z06", "d06")]);
+        assert_eq!(swapped_wires(&netlist, 2), None);
+    }
+
+    #[test]
+    fn searches_repairs_bit_by_bit() {
+        let mut netlist = adder(10, &[("z03", "a03"), ("s06", "a06")]);
+        let buses = netlist.adder_buses().unwrap();
+        assert_eq!(netlist.lowest_wrong_bit(&buses), Ok(Some(3)));
+
+        let mut swaps = vec![];
+        assert!(netlist.search_repair(&buses, 2, &mut swaps));
+        assert_eq!(swaps.len(), 2);
+        assert!(netlist
+            .verify_adder(&mut Rng::new(1), 100)
+            .unwrap()
+            .is_correct());
+        assert!(swaps[0].reason.contains("z03"));
+    }
+}
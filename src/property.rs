@@ -0,0 +1,377 @@
+/// Helpers to write property tests over puzzle inputs.
+///
+/// The generators create random inputs in the formats that puzzles commonly use. [`assert_agree`]
+/// then checks that two implementations of a part return the same answer for each of them, e.g. an
+/// optimized solution and a naive reference implementation:
+///
+/// ```ignore
+/// #[test]
+/// fn part_one_agrees_with_reference() {
+///     property::assert_agree(|rng| property::graph(rng, 40, 0.2), part_one, naive_part_one);
+/// }
+/// ```
+///
+/// A failing case reports its seed and input. Every case is generated from its own seed, so
+/// setting `AOC_PROPERTY_SEED=<seed>` and `AOC_PROPERTY_CASES=1` replays exactly that case.
+use std::{
+    env,
+    fmt::Debug,
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// The number of cases that are checked if `AOC_PROPERTY_CASES` is not set.
+pub const DEFAULT_CASES: usize = 100;
+/// The seed of the first case if `AOC_PROPERTY_SEED` is not set.
+pub const DEFAULT_SEED: u64 = 2024;
+
+/// A small, seedable pseudo-random number generator (SplitMix64).
+/// Good enough to generate test inputs, but not suitable for anything security related.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `range`. Panics if the range is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(
+            !range.is_empty(),
+            "cannot pick a number from an empty range"
+        );
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// Returns an index in `0..len`. Panics if `len` is zero.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..len as u64) as usize
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        // the upper 53 bits fit into the mantissa of a f64 without rounding.
+        ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < probability
+    }
+
+    /// Returns a random item of a slice. Panics if the slice is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Shuffles a slice in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Generates a grid of open cells `.` in which every cell is a wall `#` with the probability `walls`.
+pub fn grid(rng: &mut Rng, width: usize, height: usize, walls: f64) -> String {
+    (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| if rng.chance(walls) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Generates a grid that is enclosed by walls, like the mazes of days 16 and 20.
+/// The start `S` and the end `E` are placed on two distinct cells inside of the walls.
+/// Panics if there are less than two cells inside of the walls.
+pub fn maze(rng: &mut Rng, width: usize, height: usize, walls: f64) -> String {
+    assert!(
+        width >= 3 && height >= 3 && (width - 2) * (height - 2) >= 2,
+        "a maze needs at least two cells inside of its walls"
+    );
+
+    let mut cells: Vec<Vec<char>> = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let is_border = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+                    if is_border || rng.chance(walls) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut inner: Vec<(usize, usize)> = (1..height - 1)
+        .flat_map(|y| (1..width - 1).map(move |x| (x, y)))
+        .collect();
+    rng.shuffle(&mut inner);
+
+    let (sx, sy) = inner[0];
+    let (ex, ey) = inner[1];
+    cells[sy][sx] = 'S';
+    cells[ey][ex] = 'E';
+
+    cells
+        .into_iter()
+        .map(String::from_iter)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Generates `lines` lines with `per_line` numbers each, in `range` and joined by `separator`.
+/// E.g. `numbers(rng, 1000, 2..3, 0..100_000, "   ")` generates a list like the one of day 1.
+pub fn numbers(
+    rng: &mut Rng,
+    lines: usize,
+    per_line: Range<usize>,
+    range: Range<u64>,
+    separator: &str,
+) -> String {
+    (0..lines)
+        .map(|_| {
+            let count = rng.range(per_line.start as u64..per_line.end as u64) as usize;
+            (0..count)
+                .map(|_| rng.range(range.clone()).to_string())
+                .collect::<Vec<_>>()
+                .join(separator)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Generates an undirected graph with one `ab-cd` edge per line, like the network of day 23.
+/// `nodes` distinct two letter names are connected with the probability `density` per pair.
+/// Panics if there are more than the 676 possible two letter names.
+pub fn graph(rng: &mut Rng, nodes: usize, density: f64) -> String {
+    let mut names: Vec<String> = (b'a'..=b'z')
+        .flat_map(|a| (b'a'..=b'z').map(move |b| String::from_utf8(vec![a, b]).unwrap()))
+        .collect();
+    assert!(nodes <= names.len(), "a graph can have at most 676 nodes");
+
+    rng.shuffle(&mut names);
+    names.truncate(nodes);
+
+    let mut edges: Vec<String> = vec![];
+    for (i, a) in names.iter().enumerate() {
+        for b in &names[i + 1..] {
+            if !rng.chance(density) {
+                continue;
+            }
+
+            if rng.chance(0.5) {
+                edges.push(format!("{a}-{b}"));
+            } else {
+                edges.push(format!("{b}-{a}"));
+            }
+        }
+    }
+
+    rng.shuffle(&mut edges);
+    edges.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The cases a property is checked for.
+/// Defaults to [`DEFAULT_CASES`] cases starting at [`DEFAULT_SEED`], which can be overridden
+/// with the `AOC_PROPERTY_CASES` and `AOC_PROPERTY_SEED` environment variables.
+#[derive(Clone, Copy, Debug)]
+pub struct Cases {
+    pub count: usize,
+    pub seed: u64,
+}
+
+impl Default for Cases {
+    fn default() -> Self {
+        Self {
+            count: env_var("AOC_PROPERTY_CASES").unwrap_or(DEFAULT_CASES),
+            seed: env_var("AOC_PROPERTY_SEED").unwrap_or(DEFAULT_SEED),
+        }
+    }
+}
+
+impl Cases {
+    /// Asserts that `a` and `b` return the same result for every generated input.
+    pub fn assert_agree<T: PartialEq + Debug>(
+        &self,
+        generate: impl Fn(&mut Rng) -> String,
+        a: impl Fn(&str) -> T,
+        b: impl Fn(&str) -> T,
+    ) {
+        self.check(generate, |input| {
+            let (a, b) = (catch(|| a(input))?, catch(|| b(input))?);
+            if a == b {
+                Ok(())
+            } else {
+                Err(format!("implementations disagree: {a:?} != {b:?}"))
+            }
+        });
+    }
+
+    /// Asserts that `property` holds for every generated input.
+    pub fn assert_holds(
+        &self,
+        generate: impl Fn(&mut Rng) -> String,
+        property: impl Fn(&str) -> bool,
+    ) {
+        self.check(generate, |input| {
+            if catch(|| property(input))? {
+                Ok(())
+            } else {
+                Err("property does not hold".into())
+            }
+        });
+    }
+
+    fn check(
+        &self,
+        generate: impl Fn(&mut Rng) -> String,
+        check: impl Fn(&str) -> Result<(), String>,
+    ) {
+        for case in 0..self.count {
+            let seed = self.seed.wrapping_add(case as u64);
+            let input = generate(&mut Rng::new(seed));
+
+            if let Err(e) = check(&input) {
+                panic!(
+                    "case {case} failed: {e}\n\
+                    replay it with `AOC_PROPERTY_SEED={seed} AOC_PROPERTY_CASES=1`.\n\
+                    input:\n{input}"
+                );
+            }
+        }
+    }
+}
+
+/// Asserts that `a` and `b` return the same result for the [default cases](Cases::default).
+pub fn assert_agree<T: PartialEq + Debug>(
+    generate: impl Fn(&mut Rng) -> String,
+    a: impl Fn(&str) -> T,
+    b: impl Fn(&str) -> T,
+) {
+    Cases::default().assert_agree(generate, a, b);
+}
+
+/// Asserts that `property` holds for the [default cases](Cases::default).
+pub fn assert_holds(generate: impl Fn(&mut Rng) -> String, property: impl Fn(&str) -> bool) {
+    Cases::default().assert_holds(generate, property);
+}
+
+/// Runs a function and turns a panic into an error, so that the failing input can be reported.
+fn catch<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(func)).map_err(|e| {
+        let message = e
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| e.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        format!("panicked: {message}")
+    })
+}
+
+fn env_var<T: std::str::FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok().and_then(|value| value.parse().ok())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_deterministic_numbers() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+            assert!((10..20).contains(&a.range(10..20)));
+            b.range(10..20);
+        }
+    }
+
+    #[test]
+    fn shuffles_items() {
+        let mut items: Vec<u32> = (0..50).collect();
+        Rng::new(1).shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn generates_mazes() {
+        let maze = maze(&mut Rng::new(1), 6, 4, 0.3);
+        let lines: Vec<&str> = maze.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|line| line.len() == 6));
+        assert_eq!(lines[0], "######");
+        assert_eq!(lines[3], "######");
+        assert_eq!(maze.matches('S').count(), 1);
+        assert_eq!(maze.matches('E').count(), 1);
+    }
+
+    #[test]
+    fn generates_numbers() {
+        let numbers = numbers(&mut Rng::new(1), 20, 2..5, 5..10, " ");
+        let lines: Vec<&str> = numbers.lines().collect();
+
+        assert_eq!(lines.len(), 20);
+        for line in lines {
+            let line: Vec<u64> = line.split(' ').map(|n| n.parse().unwrap()).collect();
+            assert!((2..5).contains(&line.len()));
+            assert!(line.iter().all(|n| (5..10).contains(n)));
+        }
+    }
+
+    #[test]
+    fn generates_graphs() {
+        let graph = graph(&mut Rng::new(1), 10, 1.0);
+        assert_eq!(graph.lines().count(), 45);
+        assert!(graph
+            .lines()
+            .all(|line| line.len() == 5 && &line[2..3] == "-"));
+    }
+
+    #[test]
+    fn asserts_agreement() {
+        let cases = Cases { count: 20, seed: 1 };
+        cases.assert_agree(
+            |rng| numbers(rng, 10, 1..2, 0..100, ""),
+            |input| {
+                input
+                    .lines()
+                    .map(|n| n.parse::<u64>().unwrap())
+                    .sum::<u64>()
+            },
+            |input| input.lines().rev().map(|n| n.parse::<u64>().unwrap()).sum(),
+        );
+        cases.assert_holds(|rng| grid(rng, 3, 3, 0.5), |input| input.len() == 11);
+    }
+
+    #[test]
+    #[should_panic(expected = "implementations disagree")]
+    fn detects_disagreement() {
+        Cases { count: 20, seed: 1 }.assert_agree(
+            |rng| numbers(rng, 10, 1..2, 0..100, ""),
+            |input| input.lines().count(),
+            |input| input.lines().filter(|n| n.len() > 1).count(),
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    io::{self, Write},
+    process, thread,
+    time::Duration,
+};
+
+use chrono::{Datelike, Utc};
+
+use crate::template::{
+    commands::{download, examples, read, scaffold},
+    config, Day, ANSI_BOLD, ANSI_RESET,
+};
+
+const ANSI_CLEAR_LINE: &str = "\r\x1b[2K";
+
+/// Grace period after the unlock before the puzzle is fetched.
+const UNLOCK_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// Waits for the next puzzle to unlock, then scaffolds, downloads and reads it.
+/// Exits if the puzzle belongs to another year than the configured one.
+pub fn handle() {
+    let (day, unlock) = Day::next_unlock();
+
+    // the puzzle is fetched with the configured year, refuse to wait for another one.
+    let year = unlock.year();
+    if let Some(configured) = config::get().year.filter(|&y| i32::from(y) != year) {
+        eprintln!(
+            "Day {day} of {year} unlocks next, but the year is set to {configured}. \
+            Use `--year {year}` or update `aoc.toml` to wait for it."
+        );
+        process::exit(1);
+    }
+
+    while let Ok(remaining) = (unlock - Utc::now()).to_std() {
+        print!(
+            "{ANSI_CLEAR_LINE}🎄 Day {ANSI_BOLD}{day}{ANSI_RESET} unlocks in {ANSI_BOLD}{}{ANSI_RESET} ",
+            format_remaining(remaining)
+        );
+        io::stdout().flush().unwrap();
+
+        // wake up on the full second so the countdown ticks evenly.
+        let sleep = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        thread::sleep(if sleep.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            sleep
+        });
+    }
+
+    println!(
+        "\r🎄 Day {ANSI_BOLD}{day}{ANSI_RESET} is unlocked!{}",
+        " ".repeat(20)
+    );
+    thread::sleep(UNLOCK_GRACE_PERIOD);
+
+    scaffold::handle(day, false, None, None);
+    download::handle(day);
+    examples::handle(day);
+    read::handle(day);
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_remaining;
+    use std::time::Duration;
+
+    #[test]
+    fn formats_remaining_time() {
+        assert_eq!(format_remaining(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(format_remaining(Duration::from_secs(3_661)), "01:01:01");
+        assert_eq!(format_remaining(Duration::from_secs(90_061)), "1d 01:01:01");
+    }
+}
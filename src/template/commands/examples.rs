@@ -0,0 +1,83 @@
+use std::{collections::HashMap, fs, process, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    config,
+    module_templates::{self, TemplateVars},
+    puzzle, Day,
+};
+
+/// Pre-fills the example and the expected example answers of a day from its downloaded puzzle description.
+/// Existing examples and answers are kept, which allows to re-run this once part two is unlocked.
+pub fn handle(day: Day) {
+    let Some(example) = puzzle::example(day) else {
+        eprintln!("Could not read puzzle description. Try running `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let paths = &config::get().paths;
+    let example_path = paths.example(day).display().to_string();
+    let expected_path = paths.expected(day).display().to_string();
+
+    let is_example_empty = fs::read_to_string(&example_path).map_or(true, |s| s.trim().is_empty());
+
+    match (is_example_empty, &example.input) {
+        (true, Some(input)) => match fs::write(&example_path, input) {
+            Ok(()) => {
+                println!("Extracted example to \"{}\"", &example_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        },
+        (true, None) => {
+            println!("Could not find an example in the puzzle description.");
+        }
+        (false, _) => {}
+    }
+
+    let (part_one, part_two) = read_expected(&expected_path);
+
+    let mut vars = TemplateVars::new(day);
+    vars.part_one_example = part_one.or(example.part_one);
+    vars.part_two_example = part_two.or(example.part_two);
+
+    match fs::write(&expected_path, module_templates::render_expected(&vars)) {
+        Ok(()) => {
+            println!(
+                "Updated expected answers in \"{}\" (part 1: {}, part 2: {})",
+                &expected_path,
+                vars.part_one_example.as_deref().unwrap_or("-"),
+                vars.part_two_example.as_deref().unwrap_or("-")
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to write expected answers: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Reads the answers that are already present in a sidecar. `null` and missing answers are `None`.
+fn read_expected(path: &str) -> (Option<String>, Option<String>) {
+    let json = fs::read_to_string(path)
+        .ok()
+        .and_then(|s| JsonValue::from_str(&s).ok());
+
+    let answer = |part: &str| {
+        let value = json
+            .as_ref()?
+            .get::<HashMap<String, JsonValue>>()?
+            .get(part)?;
+
+        match value {
+            JsonValue::String(s) => Some(s.clone()),
+            JsonValue::Number(n) => Some(format!("{n:.0}")),
+            _ => None,
+        }
+    };
+
+    (answer("part_one"), answer("part_two"))
+}
@@ -1,6 +1,14 @@
 pub mod all;
+#[cfg(feature = "today")]
+pub mod countdown;
 pub mod download;
+pub mod examples;
+pub mod profile_check;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod watch;
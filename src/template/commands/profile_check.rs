@@ -0,0 +1,115 @@
+use std::{path::Path, process};
+
+use crate::template::{
+    all_days,
+    input::Input,
+    run_multi::{
+        child_commands::{self, CapturedRun},
+        get_path_for_bin, print_table,
+    },
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Runs solutions in debug and release mode and reports answers that differ between the builds.
+/// Debug builds panic on integer overflows while release builds silently wrap around, so a
+/// mismatch or a panic that only happens in debug usually points to an overflow.
+/// Checks all days if no day is given. Exits if the input is read from stdin.
+pub fn handle(day: Option<Day>, input: &Input) {
+    // both builds would need the same input, but stdin can only be read once.
+    if *input == Input::Stdin {
+        eprintln!(
+            "`profile-check` cannot read the input from stdin, use an input name or a file path."
+        );
+        process::exit(1);
+    }
+
+    let mut rows: Vec<[String; 4]> = vec![];
+    let mut failures = 0;
+
+    for day in all_days().filter(|d| day.is_none_or(|day| day == *d)) {
+        if !Path::new(&get_path_for_bin(day)).exists() || !input.exists(day) {
+            continue;
+        }
+
+        eprint!("\r{ANSI_ITALIC}Checking day {day} (debug)...  {ANSI_RESET}");
+        let debug = capture(day, false, input);
+        eprint!("\r{ANSI_ITALIC}Checking day {day} (release)...{ANSI_RESET}");
+        let release = capture(day, true, input);
+
+        let result = compare(&debug, &release);
+        if result.is_some() {
+            failures += 1;
+        }
+
+        rows.push([
+            day.to_string(),
+            format_answers(&debug),
+            format_answers(&release),
+            result.unwrap_or("✔".into()),
+        ]);
+    }
+
+    eprint!("\r{}\r", " ".repeat(40));
+
+    if rows.is_empty() {
+        println!("No solutions with input \"{input}\" found.");
+        return;
+    }
+
+    print_table(["Day", "Debug", "Release", "Result"], &rows);
+    println!();
+
+    if failures == 0 {
+        println!("{ANSI_BOLD}Debug and release builds agree on every checked day.{ANSI_RESET}");
+    } else {
+        println!(
+            "{ANSI_BOLD}{failures} of {} days differ between debug and release.{ANSI_RESET}",
+            rows.len()
+        );
+        process::exit(1);
+    }
+}
+
+fn capture(day: Day, is_release: bool, input: &Input) -> CapturedRun {
+    child_commands::capture_solution(day, is_release, input).unwrap_or_else(|e| CapturedRun {
+        output: vec![],
+        failure: Some(format!("could not run solution: {e:?}")),
+    })
+}
+
+/// Describes how the runs of both builds differ, if they do.
+fn compare(debug: &CapturedRun, release: &CapturedRun) -> Option<String> {
+    match (&debug.failure, &release.failure) {
+        (Some(debug), Some(release)) if debug == release => Some(format!("✖ both: {debug}")),
+        (Some(debug), Some(release)) => Some(format!("✖ debug: {debug}, release: {release}")),
+        (Some(debug), None) => Some(format!("✖ debug only: {debug}")),
+        (None, Some(release)) => Some(format!("✖ release only: {release}")),
+        (None, None) => {
+            let debug = child_commands::parse_answers(&debug.output);
+            let release = child_commands::parse_answers(&release.output);
+
+            let differing: Vec<String> = (0..2)
+                .filter(|&i| debug[i].answer != release[i].answer)
+                .map(|i| (i + 1).to_string())
+                .collect();
+
+            match differing.len() {
+                0 => None,
+                1 => Some(format!("✖ part {} differs", differing[0])),
+                _ => Some(format!("✖ parts {} differ", differing.join(" and "))),
+            }
+        }
+    }
+}
+
+fn format_answers(run: &CapturedRun) -> String {
+    if run.failure.is_some() {
+        return "✖ failed".into();
+    }
+
+    child_commands::parse_answers(&run.output)
+        .iter()
+        .map(|part| part.answer.as_deref().unwrap_or("-").to_string())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
@@ -4,10 +4,11 @@ use std::{
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::{
+    config,
+    module_templates::{self, TemplateVars},
+    puzzle, Day,
+};
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -27,9 +28,30 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
+pub fn handle(day: Day, overwrite: bool, template: Option<&str>, answer_type: Option<&str>) {
+    let module_template = match module_templates::load(template) {
+        Ok(module_template) => module_template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut vars = TemplateVars::new(day);
+    if let Some(answer_type) = answer_type {
+        vars.part_one_type = answer_type.into();
+        vars.part_two_type = answer_type.into();
+    }
+
+    // pre-fill the example if the puzzle description has already been downloaded.
+    let example = puzzle::example(day).unwrap_or_default();
+    vars.part_one_example = example.part_one;
+    vars.part_two_example = example.part_two;
+
+    let paths = &config::get().paths;
+    let input_path = paths.input(day).display().to_string();
+    let example_path = paths.example(day).display().to_string();
+    let expected_path = paths.expected(day).display().to_string();
     let module_path = format!("src/bin/{day}.rs");
 
     let mut file = match safe_create_file(&module_path, overwrite) {
@@ -40,11 +62,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module_templates::render(&module_template, &vars).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -65,15 +83,42 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
+        Ok(mut file) => match example.input {
+            Some(input) => match file.write_all(input.as_bytes()) {
+                Ok(()) => {
+                    println!("Created example file \"{}\"", &example_path);
+                }
+                Err(e) => {
+                    eprintln!("Failed to write example: {e}");
+                    process::exit(1);
+                }
+            },
+            None => {
+                println!("Created empty example file \"{}\"", &example_path);
+            }
+        },
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
         }
     }
 
+    match create_file(&expected_path) {
+        Ok(mut file) => match file.write_all(module_templates::render_expected(&vars).as_bytes()) {
+            Ok(()) => {
+                println!("Created expected answers file \"{}\"", &expected_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to write expected answers: {e}");
+                process::exit(1);
+            }
+        },
+        Err(e) => {
+            eprintln!("Failed to create expected answers file: {e}");
+            process::exit(1);
+        }
+    }
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
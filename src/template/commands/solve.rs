@@ -1,8 +1,21 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{
+    config::{self, BenchOptions},
+    input::Input,
+    run_multi::run_inputs,
+    Day,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+/// Runs the solution for a day. The solution is benched if `bench` is set.
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: &Input,
+    bench: Option<&BenchOptions>,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -21,8 +34,21 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
+
+        // forward the year in case it was overridden with `--year`.
+        if let Some(year) = config::get().year {
+            cmd_args.push("--year".to_string());
+            cmd_args.push(year.to_string());
+        }
+    }
+
+    if let Some(bench) = bench {
+        cmd_args.push("--time".to_string());
+        cmd_args.extend(bench.to_args());
     }
 
+    cmd_args.extend(input.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -32,3 +58,8 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Runs the solution for a day against every input in `data/inputs/NN.txt` and `data/inputs/NN/`.
+pub fn handle_all_inputs(day: Day, release: bool, bench: Option<&BenchOptions>) {
+    run_inputs(day, release, bench);
+}
@@ -0,0 +1,171 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::template::{
+    all_days, config,
+    input::all_inputs,
+    puzzle,
+    run_multi::{child_commands, get_path_for_bin, print_table},
+    timings::Timings,
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Prints a table with the progress of every day.
+pub fn handle() {
+    let timings = Timings::read_from_file();
+    let config = config::get();
+    let readme_stars = fs::read_to_string(&config.paths.readme)
+        .map(|readme| parse_readme_stars(&readme, &config.readme.stars_marker))
+        .unwrap_or_default();
+
+    let mut rows: Vec<[String; 7]> = vec![];
+    let mut total_stars = 0;
+    let mut complete_days = 0;
+
+    for day in all_days() {
+        eprint!("\r{ANSI_ITALIC}Checking day {day}...{ANSI_RESET}");
+
+        let is_scaffolded = Path::new(&get_path_for_bin(day)).exists();
+        let inputs = count_inputs(day);
+        let examples = count_examples(day);
+
+        let tests = if is_scaffolded {
+            match child_commands::run_tests(day, false) {
+                Ok(tests) if tests.total() == 0 => "-".to_string(),
+                Ok(tests) if tests.failed.is_empty() => format!("✔ {}", tests.total()),
+                Ok(tests) => format!("✖ {}/{}", tests.passed, tests.total()),
+                Err(_) => "✖ build".to_string(),
+            }
+        } else {
+            "-".to_string()
+        };
+
+        let timing = timings
+            .data
+            .iter()
+            .find(|t| t.day == day)
+            .map_or("-".to_string(), |t| {
+                format!("{:.2}ms", t.total_nanos / 1_000_000_f64)
+            });
+
+        let stars = readme_stars
+            .get(&day)
+            .copied()
+            .or_else(|| puzzle::stars(day))
+            .unwrap_or_default()
+            .min(2);
+
+        total_stars += stars;
+        if stars == 2 && tests.starts_with('✔') {
+            complete_days += 1;
+        }
+
+        rows.push([
+            day.to_string(),
+            mark(is_scaffolded),
+            if inputs == 0 {
+                mark(false)
+            } else {
+                format!("✔ {inputs}")
+            },
+            if examples == 0 {
+                mark(false)
+            } else {
+                format!("✔ {examples}")
+            },
+            tests,
+            timing,
+            "⭐".repeat(stars),
+        ]);
+    }
+
+    eprint!("\r\x1b[2K");
+
+    print_table(
+        [
+            "Day",
+            "Scaffolded",
+            "Inputs",
+            "Examples",
+            "Tests",
+            "Timing",
+            "Stars",
+        ],
+        &rows,
+    );
+
+    println!(
+        "\n{ANSI_BOLD}Stars:{ANSI_RESET} {total_stars}/50 {ANSI_ITALIC}({complete_days} of 25 days complete){ANSI_RESET}"
+    );
+}
+
+fn mark(value: bool) -> String {
+    if value { "✔" } else { "✖" }.to_string()
+}
+
+/// Counts the non-empty inputs of a day.
+fn count_inputs(day: Day) -> usize {
+    all_inputs(day)
+        .into_iter()
+        .filter_map(|input| input.path(day))
+        .filter(|path| fs::metadata(path).is_ok_and(|m| m.len() > 0))
+        .count()
+}
+
+/// Counts the non-empty examples of a day, e.g. `data/examples/01.txt` and `data/examples/01-2.txt`.
+fn count_examples(day: Day) -> usize {
+    let Ok(entries) = fs::read_dir(config::get().paths.examples()) else {
+        return 0;
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with(&day.to_string()))
+        })
+        .filter(|path| fs::metadata(path).is_ok_and(|m| m.len() > 0))
+        .count()
+}
+
+/// Reads the stars per day from the table that `aoc-readme-stars` maintains in the readme.
+fn parse_readme_stars(readme: &str, marker: &str) -> HashMap<Day, usize> {
+    let Some(table) = readme.split(marker).nth(1) else {
+        return HashMap::new();
+    };
+
+    table
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("| [Day ")?;
+            let (day, rest) = rest.split_once(']')?;
+            Some((day.parse().ok()?, rest.matches('⭐').count()))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_readme_stars;
+    use crate::day;
+
+    #[test]
+    fn parses_readme_stars() {
+        let readme = "# AoC
+
+<!--- advent_readme_stars table --->
+| Day | Part 1 | Part 2 |
+| :---: | :---: | :---: |
+| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |
+| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |
+<!--- advent_readme_stars table --->
+";
+        let stars = parse_readme_stars(readme, "<!--- advent_readme_stars table --->");
+        assert_eq!(stars.get(&day!(1)), Some(&2));
+        assert_eq!(stars.get(&day!(2)), Some(&1));
+        assert_eq!(stars.get(&day!(3)), None);
+    }
+}
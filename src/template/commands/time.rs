@@ -1,10 +1,12 @@
 use std::collections::HashSet;
 
-use crate::template::run_multi::run_multi;
+use crate::template::config::BenchOptions;
+use crate::template::input::Input;
+use crate::template::run_multi::{run_inputs, run_multi};
 use crate::template::timings::Timings;
 use crate::template::{all_days, readme_benchmarks, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(day: Option<Day>, run_all: bool, store: bool, input: &Input, bench: &BenchOptions) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,7 +23,7 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, Some(bench), input).unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -38,3 +40,9 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Benchmarks the solution for a day against every input in `data/inputs/NN.txt` and `data/inputs/NN/`.
+/// Timings of individual inputs are not stored.
+pub fn handle_all_inputs(day: Day, bench: &BenchOptions) {
+    run_inputs(day, true, Some(bench));
+}
@@ -0,0 +1,396 @@
+/// Interactive terminal UI to browse days, run solutions and view puzzles and timings.
+use std::{
+    io,
+    path::Path,
+    process::Command,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, List, ListItem, ListState, Paragraph, Row, Table, Tabs, Wrap},
+    DefaultTerminal, Frame,
+};
+
+use crate::template::{
+    all_days,
+    config::BenchOptions,
+    input::Input,
+    puzzle, readme_benchmarks,
+    run_multi::{child_commands, get_path_for_bin},
+    timings::Timings,
+    Day,
+};
+
+const TICK_RATE: Duration = Duration::from_millis(100);
+const VIEWS: [&str; 3] = ["Output", "Puzzle", "Timings"];
+const HELP: &str =
+    "↑/↓ day · enter run · 1/2 run part · b benchmark · s store benchmark · tab view · pgup/pgdn scroll · q quit";
+
+/// A solution run in the background.
+enum Job {
+    Run(Option<u8>),
+    Benchmark,
+}
+
+/// The result of a job, sent back to the UI thread.
+struct JobResult {
+    day: Day,
+    output: String,
+    timings: Option<Timings>,
+}
+
+struct App {
+    days: Vec<Day>,
+    list: ListState,
+    view: usize,
+    scroll: u16,
+    outputs: Vec<Option<String>>,
+    timings: Timings,
+    /// Timings of the last benchmark, until they are stored with `s`.
+    pending: Option<(Day, Timings)>,
+    running: Option<Day>,
+    sender: Sender<JobResult>,
+    receiver: Receiver<JobResult>,
+}
+
+pub fn handle() {
+    let mut terminal = ratatui::init();
+    let result = App::new().run(&mut terminal);
+    ratatui::restore();
+
+    if let Err(e) = result {
+        eprintln!("Failed to run terminal UI: {e}");
+    }
+}
+
+impl App {
+    fn new() -> Self {
+        let days: Vec<Day> = all_days().collect();
+        let (sender, receiver) = mpsc::channel();
+
+        Self {
+            outputs: vec![None; days.len()],
+            days,
+            list: ListState::default().with_selected(Some(0)),
+            view: 0,
+            scroll: 0,
+            timings: Timings::read_from_file(),
+            pending: None,
+            running: None,
+            sender,
+            receiver,
+        }
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            while let Ok(result) = self.receiver.try_recv() {
+                self.finish(result);
+            }
+
+            if !event::poll(TICK_RATE)? {
+                continue;
+            }
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                KeyCode::Tab => self.switch_view(1),
+                KeyCode::BackTab => self.switch_view(VIEWS.len() - 1),
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                KeyCode::Enter | KeyCode::Char('r') => self.start(Job::Run(None)),
+                KeyCode::Char('1') => self.start(Job::Run(Some(1))),
+                KeyCode::Char('2') => self.start(Job::Run(Some(2))),
+                KeyCode::Char('b') => self.start(Job::Benchmark),
+                KeyCode::Char('s') => self.store(),
+                _ => {}
+            }
+        }
+    }
+
+    fn selected(&self) -> Day {
+        self.days[self.list.selected().unwrap_or_default()]
+    }
+
+    fn select(&mut self, offset: isize) {
+        let index = self.list.selected().unwrap_or_default();
+        let index = index.saturating_add_signed(offset).min(self.days.len() - 1);
+        self.list.select(Some(index));
+        self.scroll = 0;
+    }
+
+    fn switch_view(&mut self, offset: usize) {
+        self.view = (self.view + offset) % VIEWS.len();
+        self.scroll = 0;
+    }
+
+    /// Runs a job for the selected day in the background. Only one job runs at a time.
+    fn start(&mut self, job: Job) {
+        if self.running.is_some() {
+            return;
+        }
+
+        let day = self.selected();
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            self.outputs[usize::from(day.into_inner() - 1)] =
+                Some(format!("Day {day} is not scaffolded yet."));
+            return;
+        }
+
+        self.running = Some(day);
+        self.view = 0;
+        self.scroll = 0;
+
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let result = match job {
+                Job::Run(part) => JobResult {
+                    day,
+                    output: run(day, None, part),
+                    timings: None,
+                },
+                Job::Benchmark => {
+                    let output = run(day, Some(&BenchOptions::default()), None);
+                    let lines: Vec<String> = output.lines().map(String::from).collect();
+                    let timing = child_commands::parse_exec_time(&lines, day);
+                    JobResult {
+                        day,
+                        output,
+                        timings: Some(Timings { data: vec![timing] }),
+                    }
+                }
+            };
+
+            // the receiver is gone if the UI was closed in the meantime.
+            let _ = sender.send(result);
+        });
+    }
+
+    fn finish(&mut self, result: JobResult) {
+        let mut output = result.output;
+
+        if let Some(timings) = result.timings {
+            output.push_str("\nPress s to store these timings in the timings file and the readme.");
+            self.pending = Some((result.day, timings));
+        }
+
+        self.outputs[usize::from(result.day.into_inner() - 1)] = Some(output);
+        self.running = None;
+    }
+
+    /// Stores the timings of the last benchmark, see [`Timings::store_file`].
+    fn store(&mut self) {
+        let Some((day, timings)) = self.pending.take() else {
+            return;
+        };
+
+        let merged = Timings::read_from_file().merge(&timings);
+        let stored = merged.store_file().is_ok() && readme_benchmarks::update(merged).is_ok();
+        self.timings = Timings::read_from_file();
+
+        if let Some(output) = &mut self.outputs[usize::from(day.into_inner() - 1)] {
+            output.push_str(if stored {
+                "\nStored updated benchmarks."
+            } else {
+                "\nFailed to store updated benchmarks."
+            });
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [days, content] =
+            Layout::horizontal([Constraint::Length(16), Constraint::Min(0)]).areas(main);
+
+        let items: Vec<ListItem> = self
+            .days
+            .iter()
+            .map(|day| {
+                let is_scaffolded = Path::new(&get_path_for_bin(*day)).exists();
+                let marker = if self.running == Some(*day) {
+                    "…"
+                } else if self.timings.is_day_complete(*day) {
+                    "⏱"
+                } else if is_scaffolded {
+                    "✔"
+                } else {
+                    " "
+                };
+
+                let item = ListItem::new(format!("Day {day} {marker}"));
+                if is_scaffolded {
+                    item
+                } else {
+                    item.dim()
+                }
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::bordered().title(" Days "))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, days, &mut self.list);
+
+        let [tabs, body] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content);
+
+        frame.render_widget(
+            Tabs::new(VIEWS)
+                .select(self.view)
+                .highlight_style(Style::new().bold().reversed()),
+            tabs,
+        );
+
+        let day = self.selected();
+        let block = Block::bordered().title(format!(" Day {day} "));
+
+        match self.view {
+            0 => {
+                let text = match (
+                    &self.outputs[usize::from(day.into_inner() - 1)],
+                    self.running,
+                ) {
+                    (_, Some(running)) if running == day => "Running...".to_string(),
+                    (Some(output), _) => output.clone(),
+                    (None, _) => "Press enter to run this day.".to_string(),
+                };
+                self.render_text(frame, body, block, Text::raw(text));
+            }
+            1 => {
+                let text = match puzzle::read(day) {
+                    Some(puzzle) => render_markdown(&puzzle),
+                    None => Text::raw(format!(
+                        "Puzzle not downloaded. Run `cargo download {day}` to download it."
+                    )),
+                };
+                self.render_text(frame, body, block, text);
+            }
+            _ => {
+                let rows = self.timings.data.iter().map(|timing| {
+                    Row::new([
+                        timing.day.to_string(),
+                        timing.parse.clone().unwrap_or("-".into()),
+                        timing.part_1.clone().unwrap_or("-".into()),
+                        timing.part_2.clone().unwrap_or("-".into()),
+                        format!("{:.2}ms", timing.total_nanos / 1_000_000_f64),
+                    ])
+                });
+
+                let table = Table::new(rows, [Constraint::Length(12); 5])
+                    .header(Row::new(["Day", "Parse", "Part 1", "Part 2", "Total"]).bold())
+                    .block(
+                        block
+                            .title_bottom(format!(" Total: {:.2}ms ", self.timings.total_millis())),
+                    );
+                frame.render_widget(table, body);
+            }
+        }
+
+        frame.render_widget(Line::raw(HELP).dim(), help);
+    }
+
+    fn render_text(
+        &self,
+        frame: &mut Frame,
+        area: ratatui::layout::Rect,
+        block: Block,
+        text: Text,
+    ) {
+        let paragraph = Paragraph::new(text)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(paragraph, area);
+    }
+}
+
+/// Runs the solution of a day and returns its output without terminal control sequences.
+fn run(day: Day, bench: Option<&BenchOptions>, part: Option<u8>) -> String {
+    let mut args = child_commands::solution_args(day, bench, true, &Input::Default);
+
+    if let Some(part) = part {
+        args.extend(["--part".to_string(), part.to_string()]);
+    }
+
+    match Command::new("cargo").args(&args).output() {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            clean_output(&format!("{stdout}{stderr}"))
+        }
+        Err(e) => format!("Failed to run solution: {e}"),
+    }
+}
+
+/// Removes ANSI escape sequences and intermediate results that were overwritten with `\r`.
+fn clean_output(output: &str) -> String {
+    output
+        .lines()
+        .map(|line| {
+            let line = line.rsplit('\r').next().unwrap_or_default();
+            let mut cleaned = String::with_capacity(line.len());
+            let mut chars = line.chars();
+
+            while let Some(c) = chars.next() {
+                if c == '\x1b' {
+                    // skip until the final byte of the sequence, e.g. `m` in `\x1b[1m`.
+                    chars.by_ref().find(char::is_ascii_alphabetic);
+                } else {
+                    cleaned.push(c);
+                }
+            }
+
+            cleaned.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Highlights headings and emphasized answers of a puzzle description.
+fn render_markdown(puzzle: &str) -> Text<'static> {
+    puzzle
+        .lines()
+        .map(|line| {
+            if line.starts_with('#') {
+                Line::raw(line.trim_start_matches('#').trim().to_string()).bold()
+            } else if line.starts_with("Your puzzle answer was") {
+                Line::raw(line.to_string()).green()
+            } else {
+                Line::raw(line.to_string())
+            }
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::clean_output;
+
+    #[test]
+    fn cleans_output() {
+        let output =
+            "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)\nPart 2: ✖             ";
+        assert_eq!(clean_output(output), "Part 1: 42 (1.2ms)\nPart 2: ✖");
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    commands::solve,
+    config,
+    input::Input,
+    run_multi::{child_commands, get_path_for_bin, Error},
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+type Snapshot = Vec<(PathBuf, SystemTime)>;
+
+/// Polls the sources and data files of a day and re-runs its example tests and its solution on change.
+pub fn handle(day: Day, is_release: bool, input: &Input) {
+    let mut last_snapshot: Option<Snapshot> = None;
+
+    loop {
+        let snapshot = snapshot(day, input);
+
+        if last_snapshot.as_ref() != Some(&snapshot) {
+            // give editors a moment to finish writing before building.
+            if last_snapshot.is_some() {
+                thread::sleep(POLL_INTERVAL);
+            }
+
+            run_cycle(day, is_release, input);
+            last_snapshot = Some(snapshot);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn run_cycle(day: Day, is_release: bool, input: &Input) {
+    print!("{ANSI_CLEAR}");
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching, press Ctrl+C to stop){ANSI_RESET}");
+    println!("------");
+
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        println!("Not scaffolded yet.");
+        return;
+    }
+
+    match child_commands::run_tests(day, is_release) {
+        Ok(tests) => println!("{tests}"),
+        Err(Error::BuildFailed(errors)) => {
+            println!("✖ build failed");
+            eprintln!("{errors}");
+            return;
+        }
+        Err(e) => {
+            eprintln!("Failed to run tests: {e:?}");
+            return;
+        }
+    }
+
+    if !input.exists(day) {
+        println!("No input \"{input}\".");
+        return;
+    }
+
+    println!("------");
+    solve::handle(day, is_release, false, None, input, None);
+}
+
+/// Modification times of all files that affect the solution of a day.
+fn snapshot(day: Day, input: &Input) -> Snapshot {
+    let mut paths = vec![PathBuf::from(get_path_for_bin(day))];
+
+    // library sources, without the solutions of other days.
+    collect_files(Path::new("src"), &mut paths, &|path| {
+        !path.starts_with("src/bin")
+    });
+
+    let day_prefix = day.to_string();
+    let config = config::get();
+    for folder in [config.paths.inputs(), config.paths.examples()] {
+        collect_files(&folder, &mut paths, &|path| {
+            path.strip_prefix(&folder)
+                .is_ok_and(|rest| rest.to_string_lossy().starts_with(&day_prefix))
+        });
+    }
+
+    if let Some(path) = input.path(day) {
+        paths.push(path);
+    }
+
+    let mut snapshot: Snapshot = paths
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect();
+
+    snapshot.sort();
+    snapshot.dedup();
+    snapshot
+}
+
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>, filter: &dyn Fn(&Path) -> bool) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if !filter(&path) {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_files(&path, paths, filter);
+        } else {
+            paths.push(path);
+        }
+    }
+}
@@ -0,0 +1,514 @@
+/// Template-wide settings, loaded from `aoc.toml` in the project root.
+///
+/// Every setting is optional. Settings are resolved in this order, later sources taking precedence:
+///  1. the defaults of [`Config::default`].
+///  2. the `aoc.toml` file.
+///  3. environment variables and command-line flags, e.g. `AOC_YEAR` and `--year`.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use toml::{Table, Value};
+
+use crate::template::Day;
+
+pub const CONFIG_FILE_PATH: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "invalid {CONFIG_FILE_PATH}: {e}"),
+            Error::IO(e) => write!(f, "could not read {CONFIG_FILE_PATH}: {e}"),
+        }
+    }
+}
+
+/// Settings of the template.
+///
+/// ```toml
+/// year = 2024
+///
+/// [paths]
+/// data = "data"
+/// templates = "templates"
+/// timings = "data/timings.json"
+/// readme = "README.md"
+///
+/// [readme]
+/// benchmark_marker = "<!--- benchmarking table --->"
+/// stars_marker = "<!--- advent_readme_stars table --->"
+///
+/// [bench]
+/// target_time_ms = 1000
+/// min_samples = 10
+/// max_samples = 10000
+/// warmup = 0
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// The year passed to aoc-cli. Can be overridden with `AOC_YEAR` or `--year`.
+    pub year: Option<u16>,
+    pub paths: Paths,
+    pub readme: Readme,
+    pub bench: Bench,
+}
+
+/// Locations of the files the template reads and writes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Paths {
+    /// Folder that contains `inputs/`, `examples/` and `puzzles/`.
+    pub data: PathBuf,
+    /// Folder with user-defined scaffold templates.
+    pub templates: PathBuf,
+    /// Defaults to `<data>/timings.json`.
+    pub timings: PathBuf,
+    pub readme: PathBuf,
+}
+
+/// Markers that enclose the generated tables in the readme.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Readme {
+    pub benchmark_marker: String,
+    pub stars_marker: String,
+}
+
+/// Settings of the benchmark that `--time` runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bench {
+    /// Approximate time a part is benched for.
+    pub target_time: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Number of runs before sampling starts.
+    pub warmup: u32,
+}
+
+/// Overrides of the [`Bench`] settings, set with `--bench-time`, `--min-samples`, `--max-samples`
+/// and `--warmup`. These are forwarded from `cargo time` and `cargo solve --time` to the solutions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BenchOptions {
+    pub time: Option<Duration>,
+    pub min_samples: Option<u128>,
+    pub max_samples: Option<u128>,
+    pub warmup: Option<u32>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            paths: Paths {
+                data: "data".into(),
+                templates: "templates".into(),
+                timings: "data/timings.json".into(),
+                readme: "README.md".into(),
+            },
+            readme: Readme {
+                benchmark_marker: "<!--- benchmarking table --->".into(),
+                stars_marker: "<!--- advent_readme_stars table --->".into(),
+            },
+            bench: Bench {
+                target_time: Duration::from_secs(1),
+                min_samples: 10,
+                max_samples: 10_000,
+                warmup: 0,
+            },
+        }
+    }
+}
+
+/// Returns the config of the current process. It is loaded once on first use.
+/// Exits the process if `aoc.toml` is invalid.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        load().with_env().with_args(&args)
+    })
+}
+
+/// Loads the config of the current process with a year that was already parsed from the
+/// command-line, instead of reading the flags in [`get`]. Has no effect once the config is loaded.
+/// Exits the process if `aoc.toml` is invalid.
+pub fn init(year: Option<u16>) {
+    CONFIG.get_or_init(|| {
+        let mut config = load().with_env();
+        config.year = year.or(config.year);
+        config
+    });
+}
+
+fn load() -> Config {
+    Config::load(Path::new(CONFIG_FILE_PATH)).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+impl Config {
+    /// Loads a config file on top of the defaults. A missing file yields the defaults.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Parses the contents of a config file on top of the defaults.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let table: Table = s
+            .parse()
+            .map_err(|e: toml::de::Error| Error::Parser(e.message().to_string()))?;
+
+        let mut config = Self::default();
+        let mut timings_set = false;
+
+        for (key, value) in &table {
+            match (key.as_str(), value) {
+                ("year", value) => config.year = Some(parse_int(key, value)?),
+                ("paths", Value::Table(paths)) => {
+                    for (key, value) in paths {
+                        let path = PathBuf::from(parse_str(key, value)?);
+                        match key.as_str() {
+                            "data" => config.paths.data = path,
+                            "templates" => config.paths.templates = path,
+                            "timings" => {
+                                config.paths.timings = path;
+                                timings_set = true;
+                            }
+                            "readme" => config.paths.readme = path,
+                            _ => return Err(unknown_key("paths", key)),
+                        }
+                    }
+                }
+                ("readme", Value::Table(readme)) => {
+                    for (key, value) in readme {
+                        let marker = parse_str(key, value)?.to_string();
+                        match key.as_str() {
+                            "benchmark_marker" => config.readme.benchmark_marker = marker,
+                            "stars_marker" => config.readme.stars_marker = marker,
+                            _ => return Err(unknown_key("readme", key)),
+                        }
+                    }
+                }
+                ("bench", Value::Table(bench)) => {
+                    for (key, value) in bench {
+                        match key.as_str() {
+                            "target_time_ms" => {
+                                config.bench.target_time =
+                                    Duration::from_millis(parse_int(key, value)?);
+                            }
+                            "min_samples" => config.bench.min_samples = parse_int(key, value)?,
+                            "max_samples" => config.bench.max_samples = parse_int(key, value)?,
+                            "warmup" => config.bench.warmup = parse_int(key, value)?,
+                            _ => return Err(unknown_key("bench", key)),
+                        }
+                    }
+                }
+                (key @ ("paths" | "readme" | "bench"), _) => {
+                    return Err(Error::Parser(format!("expected `{key}` to be a table.")));
+                }
+                (key, _) => return Err(unknown_key("", key)),
+            }
+        }
+
+        // keep timings next to the data unless configured explicitly.
+        if !timings_set {
+            config.paths.timings = config.paths.data.join("timings.json");
+        }
+
+        if config.bench.min_samples == 0 {
+            return Err(Error::Parser(
+                "expected `bench.min_samples` to be at least 1.".into(),
+            ));
+        }
+
+        if config.bench.min_samples > config.bench.max_samples {
+            return Err(Error::Parser(
+                "expected `bench.min_samples` to be at most `bench.max_samples`.".into(),
+            ));
+        }
+
+        Ok(config)
+    }
+
+    /// Applies overrides from environment variables.
+    #[must_use]
+    pub fn with_env(mut self) -> Self {
+        if let Some(year) = env::var("AOC_YEAR").ok().and_then(|x| x.parse().ok()) {
+            self.year = Some(year);
+        }
+        self
+    }
+
+    /// Applies overrides from command-line flags.
+    #[must_use]
+    pub fn with_args(mut self, args: &[String]) -> Self {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        if let Some(year) = value_of("--year").and_then(|x| x.parse().ok()) {
+            self.year = Some(year);
+        }
+
+        BenchOptions::from_args(args).apply(&mut self.bench);
+        self
+    }
+}
+
+impl BenchOptions {
+    /// Reads the bench flags from command-line arguments, ignoring invalid values.
+    #[must_use]
+    pub fn from_args(args: &[String]) -> Self {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        Self {
+            time: value_of("--bench-time").and_then(|x| parse_duration(x).ok()),
+            min_samples: value_of("--min-samples").and_then(|x| parse_samples(x).ok()),
+            max_samples: value_of("--max-samples").and_then(|x| parse_samples(x).ok()),
+            warmup: value_of("--warmup").and_then(|x| x.parse().ok()),
+        }
+    }
+
+    /// The flags that set these options when passed to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(time) = self.time {
+            args.extend(["--bench-time".into(), format!("{}ns", time.as_nanos())]);
+        }
+        if let Some(min_samples) = self.min_samples {
+            args.extend(["--min-samples".into(), min_samples.to_string()]);
+        }
+        if let Some(max_samples) = self.max_samples {
+            args.extend(["--max-samples".into(), max_samples.to_string()]);
+        }
+        if let Some(warmup) = self.warmup {
+            args.extend(["--warmup".into(), warmup.to_string()]);
+        }
+
+        args
+    }
+
+    /// Overrides bench settings with the options that are set.
+    pub fn apply(&self, bench: &mut Bench) {
+        if let Some(time) = self.time {
+            bench.target_time = time;
+        }
+        if let Some(min_samples) = self.min_samples {
+            bench.min_samples = min_samples;
+        }
+        if let Some(max_samples) = self.max_samples {
+            bench.max_samples = max_samples;
+        }
+        if let Some(warmup) = self.warmup {
+            bench.warmup = warmup;
+        }
+    }
+}
+
+/// Parses a number of benchmark samples, which has to be at least 1.
+pub fn parse_samples(s: &str) -> Result<u128, String> {
+    s.parse()
+        .ok()
+        .filter(|&samples| samples > 0)
+        .ok_or_else(|| format!("invalid number of samples `{s}`, expected at least 1"))
+}
+
+/// Parses a duration like `500ms`, `2s`, `1.5s` or `250000ns`. Plain numbers are milliseconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    // nanoseconds are exact, they are used to forward durations to the solutions.
+    if let Some(nanos) = s.strip_suffix("ns").and_then(|n| n.trim().parse().ok()) {
+        return Ok(Duration::from_nanos(nanos));
+    }
+
+    let (value, unit) = match s.strip_suffix("ms") {
+        Some(value) => (value, 0.001),
+        None => s.strip_suffix('s').map_or((s, 0.001), |value| (value, 1.0)),
+    };
+
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value >= 0.0)
+        .map(|value| Duration::from_secs_f64(value * unit))
+        .ok_or_else(|| format!("invalid duration `{s}`, expected e.g. `500ms` or `2s`"))
+}
+
+impl Paths {
+    /// Folder of the default inputs, e.g. `data/inputs`.
+    #[must_use]
+    pub fn inputs(&self) -> PathBuf {
+        self.data.join("inputs")
+    }
+
+    /// Folder of the examples and their expected answers, e.g. `data/examples`.
+    #[must_use]
+    pub fn examples(&self) -> PathBuf {
+        self.data.join("examples")
+    }
+
+    /// Folder of the downloaded puzzle descriptions, e.g. `data/puzzles`.
+    #[must_use]
+    pub fn puzzles(&self) -> PathBuf {
+        self.data.join("puzzles")
+    }
+
+    /// Default input of a day, e.g. `data/inputs/01.txt`.
+    #[must_use]
+    pub fn input(&self, day: Day) -> PathBuf {
+        self.inputs().join(format!("{day}.txt"))
+    }
+
+    /// Example of a day, e.g. `data/examples/01.txt`.
+    #[must_use]
+    pub fn example(&self, day: Day) -> PathBuf {
+        self.examples().join(format!("{day}.txt"))
+    }
+
+    /// Expected answers of the example of a day, e.g. `data/examples/01.expected.json`.
+    #[must_use]
+    pub fn expected(&self, day: Day) -> PathBuf {
+        self.examples().join(format!("{day}.expected.json"))
+    }
+
+    /// Downloaded puzzle description of a day, e.g. `data/puzzles/01.md`.
+    #[must_use]
+    pub fn puzzle(&self, day: Day) -> PathBuf {
+        self.puzzles().join(format!("{day}.md"))
+    }
+}
+
+fn parse_str<'a>(key: &str, value: &'a Value) -> Result<&'a str, Error> {
+    value
+        .as_str()
+        .ok_or_else(|| Error::Parser(format!("expected `{key}` to be a string.")))
+}
+
+fn parse_int<T: TryFrom<i64>>(key: &str, value: &Value) -> Result<T, Error> {
+    value
+        .as_integer()
+        .and_then(|x| T::try_from(x).ok())
+        .ok_or_else(|| Error::Parser(format!("expected `{key}` to be a non-negative integer.")))
+}
+
+fn unknown_key(table: &str, key: &str) -> Error {
+    if table.is_empty() {
+        Error::Parser(format!("unknown key `{key}`."))
+    } else {
+        Error::Parser(format!("unknown key `{table}.{key}`."))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{parse_duration, parse_samples, BenchOptions, Config};
+
+    #[test]
+    fn parses_empty_config() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse(
+            "year = 2023\n\n[paths]\ndata = \"aoc\"\n\n[bench]\ntarget_time_ms = 500\nmin_samples = 5",
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.paths.data, PathBuf::from("aoc"));
+        assert_eq!(config.paths.timings, PathBuf::from("aoc/timings.json"));
+        assert_eq!(config.paths.inputs(), PathBuf::from("aoc/inputs"));
+        assert_eq!(config.bench.target_time, Duration::from_millis(500));
+        assert_eq!(config.bench.min_samples, 5);
+        assert_eq!(config.bench.max_samples, 10_000);
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(Config::parse("year = \"next\"").is_err());
+        assert!(Config::parse("[paths]\ninputs = \"x\"").is_err());
+        assert!(Config::parse("bench = 1").is_err());
+        assert!(Config::parse("[bench]\nmin_samples = 20\nmax_samples = 10").is_err());
+        assert!(Config::parse("[bench]\nmin_samples = 0").is_err());
+        assert!(Config::parse("year = ").is_err());
+    }
+
+    #[test]
+    fn overrides_config_with_args() {
+        let config = Config::parse("year = 2023").unwrap();
+        let args = ["solve".to_string(), "--year".into(), "2022".into()];
+        assert_eq!(config.with_args(&args).year, Some(2022));
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("250"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("1500ns"), Ok(Duration::from_nanos(1500)));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("-1s").is_err());
+    }
+
+    #[test]
+    fn rejects_zero_samples() {
+        assert_eq!(parse_samples("5"), Ok(5));
+        assert!(parse_samples("0").is_err());
+        assert!(parse_samples("-1").is_err());
+
+        let args = ["--min-samples".to_string(), "0".into()];
+        assert_eq!(BenchOptions::from_args(&args).min_samples, None);
+    }
+
+    #[test]
+    fn forwards_bench_options() {
+        let options = BenchOptions {
+            time: Some(Duration::from_micros(500)),
+            min_samples: None,
+            max_samples: Some(50),
+            warmup: Some(3),
+        };
+        assert_eq!(BenchOptions::from_args(&options.to_args()), options);
+
+        let config = Config::default().with_args(&options.to_args());
+        assert_eq!(config.bench.target_time, Duration::from_micros(500));
+        assert_eq!(config.bench.min_samples, 10);
+        assert_eq!(config.bench.max_samples, 50);
+        assert_eq!(config.bench.warmup, 3);
+    }
+}
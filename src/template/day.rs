@@ -3,7 +3,7 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
@@ -55,6 +55,33 @@ impl Day {
             None
         }
     }
+
+    /// Returns the next day to unlock and the time it unlocks at.
+    /// Puzzles unlock at midnight in the timezone of the server. After the 25th, this is the
+    /// 1st of december of the following year.
+    pub fn next_unlock() -> (Self, DateTime<Utc>) {
+        Self::next_unlock_after(Utc::now())
+    }
+
+    fn next_unlock_after(now: DateTime<Utc>) -> (Self, DateTime<Utc>) {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap();
+        let today = now.with_timezone(&offset).date_naive();
+
+        let (year, day) = match (today.month(), today.day()) {
+            (12, day) if day < 25 => (today.year(), day + 1),
+            (12, _) => (today.year() + 1, 1),
+            _ => (today.year(), 1),
+        };
+
+        let unlock = offset
+            .with_ymd_and_hms(year, 12, day, 0, 0, 0)
+            .single()
+            .unwrap()
+            .with_timezone(&Utc);
+
+        // NOTE: day is in range 1 to 25.
+        (Self(u8::try_from(day).unwrap()), unlock)
+    }
 }
 
 impl Display for Day {
@@ -156,6 +183,34 @@ macro_rules! day {
 mod tests {
     use super::{all_days, Day};
 
+    #[cfg(feature = "today")]
+    #[test]
+    fn next_unlock() {
+        use chrono::{TimeZone, Utc};
+
+        let at = |y, m, d, h| Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap();
+
+        // before december, the first day unlocks next.
+        assert_eq!(
+            Day::next_unlock_after(at(2024, 11, 30, 12)),
+            (Day(1), at(2024, 12, 1, 5))
+        );
+        // 04:00 UTC is still the previous day on the server.
+        assert_eq!(
+            Day::next_unlock_after(at(2024, 12, 3, 4)),
+            (Day(3), at(2024, 12, 3, 5))
+        );
+        assert_eq!(
+            Day::next_unlock_after(at(2024, 12, 3, 5)),
+            (Day(4), at(2024, 12, 4, 5))
+        );
+        // after the last day, wait for next year.
+        assert_eq!(
+            Day::next_unlock_after(at(2024, 12, 25, 6)),
+            (Day(1), at(2025, 12, 1, 5))
+        );
+    }
+
     #[test]
     fn all_days_iterator() {
         let mut iter = all_days();
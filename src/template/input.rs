@@ -0,0 +1,191 @@
+/// Resolves the puzzle input a solution runs against.
+use std::{
+    convert::Infallible,
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::template::{config, Day};
+
+/// The puzzle input a solution runs against.
+///
+/// Besides the default input at `data/inputs/NN.txt`, a day can have any number of
+/// named inputs at `data/inputs/NN/<name>.txt`, e.g. one per team member.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input {
+    /// The default input at `data/inputs/NN.txt`.
+    Default,
+    /// A named input at `data/inputs/NN/<name>.txt`.
+    Named(String),
+    /// An arbitrary file. Values that contain a path separator or a file extension are paths,
+    /// so names must not contain a dot: `--input alice.v2` reads the file `alice.v2`.
+    Path(PathBuf),
+    /// Standard input, selected with `-`.
+    Stdin,
+}
+
+impl Input {
+    /// Reads the `--input` argument passed to the current process.
+    /// Falls back to the default input if the argument is not present.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        args.iter()
+            .position(|x| x == "--input")
+            .and_then(|i| args.get(i + 1))
+            .map_or(Input::Default, |x| x.parse().unwrap())
+    }
+
+    /// Path of the file that backs this input, `None` for standard input.
+    #[must_use]
+    pub fn path(&self, day: Day) -> Option<PathBuf> {
+        match self {
+            Input::Default => Some(get_inputs_dir().join(format!("{day}.txt"))),
+            Input::Named(name) => Some(get_named_inputs_dir(day).join(format!("{name}.txt"))),
+            Input::Path(path) => Some(path.clone()),
+            Input::Stdin => None,
+        }
+    }
+
+    /// Checks whether the file that backs this input exists. Standard input always exists.
+    #[must_use]
+    pub fn exists(&self, day: Day) -> bool {
+        self.path(day).is_none_or(|path| path.is_file())
+    }
+
+    /// Reads the input for the given day to a string.
+    pub fn read(&self, day: Day) -> Result<String, io::Error> {
+        match self.path(day) {
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut buf = String::new();
+                io::stdin().read_to_string(&mut buf)?;
+                Ok(buf)
+            }
+        }
+    }
+
+    /// The arguments that select this input when passed to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Input::Default => vec![],
+            Input::Named(name) => vec!["--input".into(), name.clone()],
+            Input::Path(path) => vec!["--input".into(), path.to_string_lossy().into_owned()],
+            Input::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+}
+
+impl FromStr for Input {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "-" {
+            return Ok(Input::Stdin);
+        }
+
+        // anything that looks like a file path is used as-is, everything else is a name.
+        let is_path = s.contains(['/', '\\']) || Path::new(s).extension().is_some();
+
+        Ok(if is_path {
+            Input::Path(PathBuf::from(s))
+        } else {
+            Input::Named(s.to_string())
+        })
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input::Default => f.write_str("default"),
+            Input::Named(name) => f.write_str(name),
+            Input::Path(path) => write!(f, "{}", path.display()),
+            Input::Stdin => f.write_str("stdin"),
+        }
+    }
+}
+
+/// Lists every input available for a day: the default input if present, followed by all named inputs sorted by name.
+#[must_use]
+pub fn all_inputs(day: Day) -> Vec<Input> {
+    let mut named: Vec<Input> = fs::read_dir(get_named_inputs_dir(day))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+                .map(Input::Named)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    named.sort_unstable_by_key(ToString::to_string);
+
+    let mut inputs = vec![];
+    if Input::Default.exists(day) {
+        inputs.push(Input::Default);
+    }
+    inputs.extend(named);
+    inputs
+}
+
+fn get_inputs_dir() -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join(config::get().paths.inputs())
+}
+
+fn get_named_inputs_dir(day: Day) -> PathBuf {
+    get_inputs_dir().join(day.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Input;
+
+    #[test]
+    fn parses_names() {
+        assert_eq!("alice".parse(), Ok(Input::Named("alice".into())));
+        assert_eq!("bob_2".parse(), Ok(Input::Named("bob_2".into())));
+    }
+
+    #[test]
+    fn parses_paths() {
+        assert_eq!(
+            "data/inputs/01.txt".parse(),
+            Ok(Input::Path(PathBuf::from("data/inputs/01.txt")))
+        );
+        assert_eq!("big.txt".parse(), Ok(Input::Path(PathBuf::from("big.txt"))));
+        assert_eq!("./big".parse(), Ok(Input::Path(PathBuf::from("./big"))));
+    }
+
+    #[test]
+    fn parses_stdin() {
+        assert_eq!("-".parse(), Ok(Input::Stdin));
+        assert_eq!(
+            Input::Stdin.to_args(),
+            vec!["--input".to_string(), "-".to_string()]
+        );
+    }
+
+    #[test]
+    fn converts_to_child_args() {
+        assert!(Input::Default.to_args().is_empty());
+        assert_eq!(
+            Input::Named("alice".into()).to_args(),
+            vec!["--input".to_string(), "alice".to_string()]
+        );
+    }
+}
@@ -1,7 +1,11 @@
-use std::{env, fs};
+use std::{env, fs, process};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+pub mod input;
+pub mod module_templates;
+pub mod puzzle;
 pub mod runner;
 
 pub use day::*;
@@ -19,7 +23,10 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(&config::get().paths.data)
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -29,29 +36,117 @@ pub fn read_file(folder: &str, day: Day) -> String {
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(&config::get().paths.data)
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Helper function that reads the puzzle input selected via the `--input` argument.
+/// This can be an input name, a file path or `-` for stdin.
+/// Falls back to `data/inputs/NN.txt` if no input was selected.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let input = input::Input::from_args();
+    match input.read(day) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("could not open input \"{input}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Also sets up the example tests of the day, see [`example_tests!`](crate::example_tests).
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse` as second parameter, the day parses its input once with `parse(&str) -> Parsed`
+/// and the parts take the parsed input, i.e. `part_one(&Parsed)` and `part_two(&Parsed)`.
+/// Parsing is then timed separately from the parts. A third parameter (1 or 2) again selects a
+/// single part, e.g. `solution!(25, parse, 1)`.
+///
+/// With `shared` as second parameter, part two reuses state computed by part one. The parts are
+/// then `part_one(&str) -> (Option<T>, State)` and `part_two(&str, &State)`.
+///
+/// With `inspect` as last parameter, e.g. `solution!(16, shared, inspect)`, the day has an
+/// `inspect(&str) -> bool` function that is called once with the input before the parts run.
+/// It can show extra output that is requested with flags of the day, e.g. a visualization, without
+/// it ending up in the timed parts or in the answers that other commands read. If it returns
+/// `true`, the parts are not run.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [], [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_two, 2]);
+    };
+    ($day:expr, inspect) => {
+        $crate::solution!(@impl $day, [inspect], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse) => {
+        $crate::solution!(@parse $day, [], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse, 1) => {
+        $crate::solution!(@parse $day, [], [part_one, 1]);
+    };
+    ($day:expr, parse, 2) => {
+        $crate::solution!(@parse $day, [], [part_two, 2]);
+    };
+    ($day:expr, parse, inspect) => {
+        $crate::solution!(@parse $day, [inspect], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, shared) => {
+        $crate::solution!(@shared $day, []);
+    };
+    ($day:expr, shared, inspect) => {
+        $crate::solution!(@shared $day, [inspect]);
+    };
+
+    (@shared $day:expr, [$($inspect:ident)?]) => {
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+
+        #[cfg(feature = "dhat-heap")]
+        #[global_allocator]
+        static ALLOC: dhat::Alloc = dhat::Alloc;
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_input(DAY);
+            $( if $inspect(&input) { return; } )?
+            run_shared(part_one, part_two, &input, DAY);
+        }
+
+        $crate::example_tests!($day, shared);
+    };
+
+    (@parse $day:expr, [$($inspect:ident)?], $( [$func:expr, $part:expr] )*) => {
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+
+        #[cfg(feature = "dhat-heap")]
+        #[global_allocator]
+        static ALLOC: dhat::Alloc = dhat::Alloc;
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_input(DAY);
+            $( if $inspect(&input) { return; } )?
+            let parsed = run_parse(parse, &input);
+            $( run_part($func, &parsed, DAY, $part); )*
+        }
+
+        $crate::example_tests!($day, parse);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, [$($inspect:ident)?], $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -61,8 +156,73 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+            let input = $crate::template::read_input(DAY);
+            $( if $inspect(&input) { return; } )?
             $( run_part($func, &input, DAY, $part); )*
         }
+
+        $crate::example_tests!($day);
+    };
+}
+
+/// Creates one test per example file and part of a day.
+///
+/// The tests are generated by the build script from the sidecar files next to the examples.
+/// E.g. `data/examples/03-2.expected.json` containing `{ "part_two": 48 }` creates a test
+/// `part_two_03_2` that asserts that `part_two` returns `Some(48)` for `data/examples/03-2.txt`.
+/// With `parse` as second parameter, the examples are parsed with `parse` before solving them.
+/// With `shared` as second parameter, part two is solved with the state part one returned.
+#[macro_export]
+macro_rules! example_tests {
+    ($day:expr) => {
+        #[cfg(test)]
+        mod example_tests {
+            #[allow(unused_imports)]
+            use super::*;
+
+            /// Solves an example with a part, used by the generated tests.
+            macro_rules! solve_example {
+                ($part:ident, $input:expr) => {
+                    $part($input)
+                };
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/example_tests_", $day, ".rs"));
+        }
+    };
+    ($day:expr, parse) => {
+        #[cfg(test)]
+        mod example_tests {
+            #[allow(unused_imports)]
+            use super::*;
+
+            /// Solves an example with a part, used by the generated tests.
+            macro_rules! solve_example {
+                ($part:ident, $input:expr) => {
+                    $part(&parse($input))
+                };
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/example_tests_", $day, ".rs"));
+        }
+    };
+    ($day:expr, shared) => {
+        #[cfg(test)]
+        mod example_tests {
+            #[allow(unused_imports)]
+            use super::*;
+
+            /// Solves an example with a part, used by the generated tests.
+            macro_rules! solve_example {
+                (part_one, $input:expr) => {
+                    part_one($input).0
+                };
+                (part_two, $input:expr) => {
+                    part_two($input, &part_one($input).1)
+                };
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/example_tests_", $day, ".rs"));
+        }
     };
 }
@@ -0,0 +1,268 @@
+/// Templates that the `scaffold` command creates solution modules from.
+///
+/// Besides the default template, there are a few presets for common puzzle types. User-defined
+/// templates in `./templates/<name>.txt` take precedence over presets with the same name.
+/// The folder of user-defined templates can be changed in `aoc.toml`.
+use std::{fmt::Display, fs, io};
+
+use crate::template::{aoc_cli, config, puzzle, Day};
+
+const DEFAULT_TEMPLATE: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+const PRESETS: [(&str, &str); 4] = [
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/presets/graph.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/presets/grid.txt"
+        )),
+    ),
+    (
+        "string",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/presets/string.txt"
+        )),
+    ),
+    (
+        "vm",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/presets/vm.txt"
+        )),
+    ),
+];
+
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound(name) => write!(
+                f,
+                "template \"{name}\" not found. Available templates: {}.",
+                available().join(", ")
+            ),
+            Error::IO(e) => write!(f, "could not read template: {e}"),
+        }
+    }
+}
+
+/// Values that are substituted for the placeholders of a template.
+///
+/// | Placeholder            | Value                                                   |
+/// | ---------------------- | ------------------------------------------------------- |
+/// | `%DAY_NUMBER%`         | day without padding, e.g. `1`                           |
+/// | `%DAY%`                | day with padding, e.g. `01`                             |
+/// | `%YEAR%`               | value of `AOC_YEAR`                                     |
+/// | `%TITLE%`              | puzzle title from `data/puzzles/NN.md` if downloaded    |
+/// | `%PART_ONE_TYPE%`      | answer type of part one, `u32` by default               |
+/// | `%PART_TWO_TYPE%`      | answer type of part two, `u32` by default               |
+/// | `%PART_ONE_EXAMPLE%`   | expected example answer of part one, e.g. `Some(11)`    |
+/// | `%PART_TWO_EXAMPLE%`   | expected example answer of part two, e.g. `None`        |
+#[derive(Clone, Debug)]
+pub struct TemplateVars {
+    pub day: Day,
+    pub year: Option<u16>,
+    pub title: Option<String>,
+    pub part_one_type: String,
+    pub part_two_type: String,
+    pub part_one_example: Option<String>,
+    pub part_two_example: Option<String>,
+}
+
+impl TemplateVars {
+    /// Creates the variables for a day, reading year and title from the environment.
+    #[must_use]
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            year: aoc_cli::get_year(),
+            title: puzzle::title(day),
+            part_one_type: DEFAULT_ANSWER_TYPE.into(),
+            part_two_type: DEFAULT_ANSWER_TYPE.into(),
+            part_one_example: None,
+            part_two_example: None,
+        }
+    }
+}
+
+/// Loads a template by name. `None` loads the default template.
+pub fn load(name: Option<&str>) -> Result<String, Error> {
+    let Some(name) = name else {
+        return Ok(DEFAULT_TEMPLATE.into());
+    };
+
+    let user_path = config::get().paths.templates.join(format!("{name}.txt"));
+    if user_path.is_file() {
+        return Ok(fs::read_to_string(user_path)?);
+    }
+
+    if name == "default" {
+        return Ok(DEFAULT_TEMPLATE.into());
+    }
+
+    PRESETS
+        .iter()
+        .find(|(preset, _)| *preset == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| Error::NotFound(name.into()))
+}
+
+/// Names of all templates that can be loaded, sorted by name.
+#[must_use]
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = ["default"]
+        .into_iter()
+        .chain(PRESETS.iter().map(|(name, _)| *name))
+        .map(String::from)
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(&config::get().paths.templates) {
+        names.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_string_lossy().into_owned())),
+        );
+    }
+
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Substitutes all placeholders of a template.
+#[must_use]
+pub fn render(template: &str, vars: &TemplateVars) -> String {
+    template
+        .replace("%DAY_NUMBER%", &vars.day.into_inner().to_string())
+        .replace("%DAY%", &vars.day.to_string())
+        .replace(
+            "%YEAR%",
+            &vars.year.map(|year| year.to_string()).unwrap_or_default(),
+        )
+        .replace(
+            "%TITLE%",
+            vars.title
+                .as_deref()
+                .unwrap_or(&format!("Day {}", vars.day.into_inner())),
+        )
+        .replace("%PART_ONE_TYPE%", &vars.part_one_type)
+        .replace("%PART_TWO_TYPE%", &vars.part_two_type)
+        .replace(
+            "%PART_ONE_EXAMPLE%",
+            &render_answer(vars.part_one_example.as_deref(), &vars.part_one_type),
+        )
+        .replace(
+            "%PART_TWO_EXAMPLE%",
+            &render_answer(vars.part_two_example.as_deref(), &vars.part_two_type),
+        )
+}
+
+/// Renders the contents of the `data/examples/NN.expected.json` sidecar.
+#[must_use]
+pub fn render_expected(vars: &TemplateVars) -> String {
+    let to_json = |answer: Option<&str>| match answer {
+        Some(answer) if answer.parse::<u64>().is_ok_and(|n| n < 1 << 53) => answer.to_string(),
+        Some(answer) => format!("{answer:?}"),
+        None => "null".into(),
+    };
+
+    format!(
+        "{{\n    \"part_one\": {},\n    \"part_two\": {}\n}}\n",
+        to_json(vars.part_one_example.as_deref()),
+        to_json(vars.part_two_example.as_deref())
+    )
+}
+
+/// Renders an answer as a Rust expression of type `Option<T>`.
+fn render_answer(answer: Option<&str>, answer_type: &str) -> String {
+    match answer {
+        Some(answer) if answer_type != "String" && answer.parse::<i128>().is_ok() => {
+            format!("Some({answer})")
+        }
+        Some(answer) => format!("Some({answer:?}.to_string())"),
+        None => "None".into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, render, render_expected, TemplateVars};
+    use crate::day;
+
+    fn get_mock_vars() -> TemplateVars {
+        TemplateVars {
+            day: day!(3),
+            year: Some(2024),
+            title: Some("Mull It Over".into()),
+            part_one_type: "u64".into(),
+            part_two_type: "String".into(),
+            part_one_example: Some("161".into()),
+            part_two_example: None,
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let template = "%DAY_NUMBER% %DAY% %YEAR% %TITLE% %PART_ONE_TYPE% %PART_TWO_TYPE%";
+        assert_eq!(
+            render(template, &get_mock_vars()),
+            "3 03 2024 Mull It Over u64 String"
+        );
+    }
+
+    #[test]
+    fn renders_example_answers() {
+        let mut vars = get_mock_vars();
+        let template = "%PART_ONE_EXAMPLE% %PART_TWO_EXAMPLE%";
+        assert_eq!(render(template, &vars), "Some(161) None");
+
+        vars.part_two_example = Some("ab,cd".into());
+        assert_eq!(
+            render(template, &vars),
+            "Some(161) Some(\"ab,cd\".to_string())"
+        );
+    }
+
+    #[test]
+    fn renders_expected_answers() {
+        let mut vars = get_mock_vars();
+        vars.part_two_example = Some("ab,cd".into());
+        assert_eq!(
+            render_expected(&vars),
+            "{\n    \"part_one\": 161,\n    \"part_two\": \"ab,cd\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn loads_presets() {
+        assert!(load(None).unwrap().contains("%DAY_NUMBER%"));
+        assert!(load(Some("grid")).unwrap().contains("struct Grid"));
+        assert!(load(Some("does-not-exist")).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+/// Extracts information from puzzle descriptions downloaded to `data/puzzles/NN.md`.
+use std::fs;
+
+use regex::Regex;
+
+use crate::template::{aoc_cli, Day};
+
+/// The example of a puzzle and the answers the description gives for it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: Option<String>,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+/// Reads the downloaded description of a puzzle, if present.
+#[must_use]
+pub fn read(day: Day) -> Option<String> {
+    fs::read_to_string(aoc_cli::get_puzzle_path(day)).ok()
+}
+
+/// Reads the puzzle title from a downloaded puzzle description, e.g. `Historian Hysteria`.
+#[must_use]
+pub fn title(day: Day) -> Option<String> {
+    parse_title(&read(day)?)
+}
+
+/// Extracts the example from a downloaded puzzle description.
+#[must_use]
+pub fn example(day: Day) -> Option<Example> {
+    Some(parse_example(&read(day)?))
+}
+
+/// Counts the stars of a puzzle from a downloaded description. Descriptions downloaded after
+/// solving a part contain its accepted answer.
+#[must_use]
+pub fn stars(day: Day) -> Option<usize> {
+    Some(parse_stars(&read(day)?))
+}
+
+fn parse_stars(puzzle: &str) -> usize {
+    puzzle.matches("Your puzzle answer was").count()
+}
+
+fn parse_title(puzzle: &str) -> Option<String> {
+    let line = puzzle.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = line.split_once(": ")?;
+    Some(title.trim().trim_end_matches('-').trim().to_string())
+}
+
+/// The example input is the largest code block of part one. The answer of a part is the last
+/// emphasized code span of its section, which is how the puzzles highlight results.
+fn parse_example(puzzle: &str) -> Example {
+    let (part_one, part_two) = match puzzle.find("--- Part Two ---") {
+        Some(index) => (&puzzle[..index], Some(&puzzle[index..])),
+        None => (puzzle, None),
+    };
+
+    Example {
+        input: code_blocks(part_one).into_iter().max_by_key(String::len),
+        part_one: last_answer(part_one),
+        part_two: part_two.and_then(last_answer),
+    }
+}
+
+/// Collects fenced (` ``` `), backtick-delimited and indented code blocks.
+fn code_blocks(section: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut lines = section.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            let fence = &trimmed[..3];
+            let block: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !line.trim().starts_with(fence))
+                .collect();
+            blocks.push(block.join("\n"));
+        } else if trimmed == "`" {
+            let block: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| line.trim() != "`")
+                .collect();
+            blocks.push(block.join("\n"));
+        } else if let Some(indented) = line.strip_prefix("    ") {
+            let mut block = vec![indented];
+            while let Some(indented) = lines.peek().and_then(|line| line.strip_prefix("    ")) {
+                block.push(indented);
+                lines.next();
+            }
+            blocks.push(block.join("\n"));
+        }
+    }
+
+    blocks
+        .into_iter()
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| block + "\n")
+        .collect()
+}
+
+fn last_answer(section: &str) -> Option<String> {
+    // matches `*42*`, *`42`* and <code><em>42</em></code>.
+    let re = Regex::new(r"`\*+([^`*\n]+)\*+`|\*+`([^`\n]+)`\*+|<code><em>([^<\n]+)</em></code>")
+        .unwrap();
+
+    re.captures_iter(section)
+        .filter_map(|c| c.iter().skip(1).flatten().next())
+        .map(|m| m.as_str().trim().to_string())
+        .last()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_example, parse_stars, parse_title, Example};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+For example:
+
+```
+3   4
+4   3
+2   5
+```
+
+Pairing them up gives `1`. In the example above, this is `*11*`!
+
+## --- Part Two ---
+
+Given `x`, the similarity score is *`31`*.
+";
+
+    #[test]
+    fn parses_titles() {
+        let puzzle = "## \\--- Day 3: Mull It Over ---\n\n\"Our computers are having issues...";
+        assert_eq!(parse_title(puzzle), Some("Mull It Over".into()));
+        assert_eq!(parse_title("no title"), None);
+    }
+
+    #[test]
+    fn parses_stars() {
+        assert_eq!(parse_stars(PUZZLE), 0);
+        let solved = format!("{PUZZLE}\nYour puzzle answer was `11`.\n");
+        assert_eq!(parse_stars(&solved), 1);
+    }
+
+    #[test]
+    fn parses_examples() {
+        assert_eq!(
+            parse_example(PUZZLE),
+            Example {
+                input: Some("3   4\n4   3\n2   5\n".into()),
+                part_one: Some("11".into()),
+                part_two: Some("31".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_examples_before_part_two() {
+        let puzzle = PUZZLE.split("## --- Part Two").next().unwrap();
+        let example = parse_example(puzzle);
+        assert_eq!(example.part_one, Some("11".into()));
+        assert_eq!(example.part_two, None);
+    }
+
+    #[test]
+    fn picks_largest_code_block() {
+        let puzzle =
+            "For example:\n\n    ab\n    cd\n\nor\n\n`\n1\n`\n\nresult <code><em>5</em></code>";
+        let example = parse_example(puzzle);
+        assert_eq!(example.input, Some("ab\ncd\n".into()));
+        assert_eq!(example.part_one, Some("5".into()));
+    }
+}
@@ -1,13 +1,25 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, io, process};
 
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    config::BenchOptions,
+    input::{all_inputs, Input},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    bench: Option<&BenchOptions>,
+    input: &Input,
+) -> Option<Timings> {
+    if *input == Input::Stdin && days_to_run.len() > 1 {
+        eprintln!("Reading input from stdin is only supported when running a single day.");
+        process::exit(1);
+    }
+
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,7 +36,13 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            // skip days that do not have the requested input.
+            if *input != Input::Default && !input.exists(day) {
+                println!("No input \"{input}\".");
+                return;
+            }
+
+            let output = child_commands::run_solution(day, bench, is_release, input).unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
@@ -34,7 +52,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             }
         });
 
-    if is_timed {
+    if bench.is_some() {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -46,10 +64,67 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Runs a single day against every one of its inputs and prints a summary of the answers and timings per input.
+pub fn run_inputs(day: Day, is_release: bool, bench: Option<&BenchOptions>) {
+    let inputs = all_inputs(day);
+
+    if inputs.is_empty() {
+        println!("No inputs found for day {day}.");
+        return;
+    }
+
+    let mut rows: Vec<[String; 3]> = Vec::with_capacity(inputs.len());
+
+    for input in &inputs {
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}({input}){ANSI_RESET}");
+        println!("------");
+
+        let output = child_commands::run_solution(day, bench, is_release, input).unwrap();
+        let [part_1, part_2] = child_commands::parse_answers(&output);
+
+        rows.push([input.to_string(), part_1.to_string(), part_2.to_string()]);
+        println!();
+    }
+
+    print_table(["Input", "Part 1", "Part 2"], &rows);
+}
+
+/// Prints rows as a table with aligned columns and a bold header.
+pub fn print_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) {
+    let header = header.map(String::from);
+    let widths: Vec<usize> = (0..N)
+        .map(|col| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    for (i, row) in [&header].into_iter().chain(rows).enumerate() {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string();
+
+        if i == 0 {
+            println!("{ANSI_BOLD}{line}{ANSI_RESET}");
+        } else {
+            println!("{line}");
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    BuildFailed(String),
     IO(io::Error),
 }
 
@@ -68,34 +143,60 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{config::BenchOptions, input::Input, Day, ANSI_BOLD, ANSI_RESET};
     use std::{
+        fmt::Display,
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Arguments of the `cargo` invocation that runs the solution bin for a given day.
+    /// Arguments appended to these are passed to the solution. Solutions are benched if `bench` is set.
+    pub fn solution_args(
+        day: Day,
+        bench: Option<&BenchOptions>,
+        is_release: bool,
+        input: &Input,
+    ) -> Vec<String> {
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
-        if is_timed {
+        args.push("--".into());
+
+        if let Some(bench) = bench {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".into());
+            args.extend(bench.to_args());
         }
 
+        args.extend(input.to_args());
+        args
+    }
+
+    /// Run the solution bin for a given day
+    pub fn run_solution(
+        day: Day,
+        bench: Option<&BenchOptions>,
+        is_release: bool,
+        input: &Input,
+    ) -> Result<Vec<String>, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(vec![]);
+        }
+
+        let args = solution_args(day, bench, is_release, input);
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -128,9 +229,128 @@ pub mod child_commands {
         Ok(output)
     }
 
+    /// Run the solution bin for a given day without forwarding its output.
+    /// A failed run, e.g. a panic, is reported in `failure` instead of as an error.
+    pub fn capture_solution(
+        day: Day,
+        is_release: bool,
+        input: &Input,
+    ) -> Result<CapturedRun, Error> {
+        let args = solution_args(day, None, is_release, input);
+        let output = Command::new("cargo").args(&args).output()?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        let failure = parse_panic(&stderr).or_else(|| {
+            (!output.status.success()).then(|| format!("failed with {}", output.status))
+        });
+
+        Ok(CapturedRun {
+            output: String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(String::from)
+                .collect(),
+            failure,
+        })
+    }
+
+    /// The output of a solution run with [`capture_solution`].
+    #[derive(Debug, Default)]
+    pub struct CapturedRun {
+        pub output: Vec<String>,
+        pub failure: Option<String>,
+    }
+
+    /// Extracts the message and location of a panic from the stderr of a solution.
+    pub fn parse_panic(stderr: &str) -> Option<String> {
+        let mut lines = stderr.lines();
+        let location = lines.find_map(|line| line.split_once(" panicked at ").map(|(_, l)| l))?;
+        let location = location.trim_end_matches(':');
+
+        match lines.next() {
+            Some(message) if !message.starts_with("note:") => {
+                Some(format!("{message} ({location})"))
+            }
+            _ => Some(format!("panicked at {location}")),
+        }
+    }
+
+    /// Run the example tests of the solution bin for a given day.
+    pub fn run_tests(day: Day, is_release: bool) -> Result<TestSummary, Error> {
+        let day_padded = day.to_string();
+        let mut args = vec!["test", "--bin", &day_padded];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        let output = Command::new("cargo").args(&args).output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        // test binaries always print a result line, even if tests fail.
+        if !stdout.contains("test result:") {
+            let errors = String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .skip_while(|line| !line.starts_with("error"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Err(Error::BuildFailed(errors));
+        }
+
+        Ok(parse_tests(&stdout))
+    }
+
+    /// The outcome of the example tests of a day.
+    #[derive(Debug, Default, PartialEq)]
+    pub struct TestSummary {
+        pub passed: usize,
+        pub failed: Vec<String>,
+    }
+
+    impl TestSummary {
+        pub fn total(&self) -> usize {
+            self.passed + self.failed.len()
+        }
+    }
+
+    impl Display for TestSummary {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            if self.failed.is_empty() {
+                write!(f, "✔ {} tests passed", self.passed)
+            } else {
+                write!(
+                    f,
+                    "✖ {} of {} tests failed: {}",
+                    self.failed.len(),
+                    self.total(),
+                    self.failed.join(", ")
+                )
+            }
+        }
+    }
+
+    pub fn parse_tests(output: &str) -> TestSummary {
+        let mut summary = TestSummary::default();
+
+        for line in output.lines() {
+            let Some(line) = line.strip_prefix("test ") else {
+                continue;
+            };
+
+            if line.ends_with(" ... ok") {
+                summary.passed += 1;
+            } else if let Some(name) = line.strip_suffix(" ... FAILED") {
+                let name = name.rsplit("::").next().unwrap_or(name);
+                summary.failed.push(name.to_string());
+            }
+        }
+
+        summary
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
@@ -152,7 +372,9 @@ pub mod child_commands {
                 Some((part, timing_str, nanos))
             })
             .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
+                if part.contains("Parse") {
+                    timings.parse = Some(timing_str.into());
+                } else if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
@@ -164,6 +386,55 @@ pub mod child_commands {
         timings
     }
 
+    /// The answer and timing a solution printed for one part.
+    #[derive(Debug, Default, PartialEq)]
+    pub struct PartOutput {
+        pub answer: Option<String>,
+        pub time: Option<String>,
+    }
+
+    impl Display for PartOutput {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match (&self.answer, &self.time) {
+                (Some(answer), Some(time)) => write!(f, "{answer} ({time})"),
+                (Some(answer), None) => f.write_str(answer),
+                (None, _) => f.write_str("✖"),
+            }
+        }
+    }
+
+    pub fn parse_answers(output: &[String]) -> [PartOutput; 2] {
+        let mut parts: [PartOutput; 2] = Default::default();
+
+        for line in output {
+            // the runner prints an intermediate result first and overwrites it with `\r`.
+            let line = line.rsplit('\r').next().unwrap_or_default();
+
+            let index = if line.starts_with("Part 1:") {
+                0
+            } else if line.starts_with("Part 2:") {
+                1
+            } else {
+                continue;
+            };
+
+            let answer = line
+                .split_once(ANSI_BOLD)
+                .and_then(|(_, rest)| rest.split_once(ANSI_RESET))
+                .map(|(answer, _)| answer.to_string())
+                .or_else(|| line.contains('▼').then(|| "▼".to_string()));
+
+            let time = answer.as_ref().and_then(|_| {
+                let time = line.rsplit_once('(')?.1.strip_suffix(')')?;
+                Some(time.split(" @ ").next()?.to_string())
+            });
+
+            parts[index] = PartOutput { answer, time };
+        }
+
+        parts
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -174,7 +445,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -205,7 +476,9 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{
+            parse_answers, parse_exec_time, parse_panic, parse_tests, PartOutput, TestSummary,
+        };
 
         use crate::day;
 
@@ -224,6 +497,21 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "74.13ms");
         }
 
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: ✔ (2ms @ 10 samples)".into(),
+                    "Part 1: 0 (1ms @ 10 samples)".into(),
+                    "Part 2: 10 (1ms @ 10 samples)".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 4000000_f64);
+            assert_eq!(res.parse.unwrap(), "2ms");
+            assert_eq!(res.part_1.unwrap(), "1ms");
+        }
+
         #[test]
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
@@ -253,5 +541,70 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(&[
+                "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)".into(),
+                "Part 2: \x1b[1mab,cd\x1b[0m\rPart 2: \x1b[1mab,cd\x1b[0m (74.13ns @ 100 samples)"
+                    .into(),
+                "".into(),
+            ]);
+            assert_eq!(
+                res[0],
+                PartOutput {
+                    answer: Some("42".into()),
+                    time: Some("1.2ms".into())
+                }
+            );
+            assert_eq!(
+                res[1],
+                PartOutput {
+                    answer: Some("ab,cd".into()),
+                    time: Some("74.13ns".into())
+                }
+            );
+        }
+
+        #[test]
+        fn parses_missing_answers() {
+            let res = parse_answers(&["Part 1: ✖\rPart 1: ✖             ".into()]);
+            assert_eq!(res[0], PartOutput::default());
+            assert_eq!(res[1], PartOutput::default());
+        }
+
+        #[test]
+        fn parses_tests() {
+            let res = parse_tests(
+                "running 2 tests\n\
+                test example_tests::part_one_01 ... ok\n\
+                test example_tests::part_two_01 ... FAILED\n\
+                \n\
+                test result: FAILED. 1 passed; 1 failed;",
+            );
+            assert_eq!(
+                res,
+                TestSummary {
+                    passed: 1,
+                    failed: vec!["part_two_01".into()]
+                }
+            );
+            assert_eq!(res.to_string(), "✖ 1 of 2 tests failed: part_two_01");
+        }
+
+        #[test]
+        fn parses_panics() {
+            let res = parse_panic(
+                "   Compiling advent_of_code v0.11.0\n\
+                thread 'main' panicked at src/bin/13.rs:42:17:\n\
+                attempt to multiply with overflow\n\
+                note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace",
+            );
+            assert_eq!(
+                res.as_deref(),
+                Some("attempt to multiply with overflow (src/bin/13.rs:42:17)")
+            );
+            assert_eq!(parse_panic("Part 1: 42"), None);
+        }
     }
 }
@@ -7,9 +7,13 @@ use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, config, Day, ANSI_ITALIC, ANSI_RESET};
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    if !is_part_selected(part) {
+        return;
+    }
+
     let part_str = format!("Part {part}");
 
     let (result, duration, samples) =
@@ -22,9 +26,75 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
     }
 }
 
+/// Runs both parts of a solution whose second part reuses state computed by the first part.
+/// `part_one` returns its answer along with the state, which `part_two` then receives. Both parts
+/// are timed individually, followed by their combined time.
+pub fn run_shared<S, T: Display, U: Display>(
+    part_one: impl Fn(&str) -> (Option<T>, S),
+    part_two: impl Fn(&str, &S) -> Option<U>,
+    input: &str,
+    day: Day,
+) {
+    // part two depends on the state of part one, so part one always runs.
+    let (state, duration_1) = if is_part_selected(1) {
+        let ((result, state), duration, samples) = run_timed(&part_one, input, |(result, _)| {
+            print_result(result, "Part 1", "");
+        });
+
+        print_result(&result, "Part 1", &format_duration(&duration, samples));
+
+        if let Some(result) = result {
+            submit_result(result, day, 1);
+        }
+
+        (state, Some(duration))
+    } else {
+        (part_one(input).1, None)
+    };
+
+    if !is_part_selected(2) {
+        return;
+    }
+
+    let (result, duration_2, samples) = run_timed(
+        |input| part_two(input, &state),
+        input,
+        |result| print_result(result, "Part 2", ""),
+    );
+
+    print_result(&result, "Part 2", &format_duration(&duration_2, samples));
+
+    if let Some(result) = result {
+        submit_result(result, day, 2);
+    }
+
+    if let Some(duration_1) = duration_1 {
+        println!("Total: ✔{}", format_duration(&(duration_1 + duration_2), 1));
+    }
+}
+
+/// Runs the parser of a solution that parses its input separately and returns the parsed input.
+/// Parsing is timed like a part.
+pub fn run_parse<T>(func: impl Fn(&str) -> T, input: &str) -> T {
+    let (parsed, duration, samples) = run_timed(func, input, |_| print!("Parse: ✔"));
+    print!("\r");
+    println!("Parse: ✔{}", format_duration(&duration, samples));
+    parsed
+}
+
+/// Checks whether a part was selected with the `--part` argument. All parts are selected by default.
+pub fn is_part_selected(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    args.iter()
+        .position(|x| x == "--part")
+        .and_then(|i| args.get(i + 1))
+        .is_none_or(|x| x.parse::<u8>().ok() == Some(part))
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer. See [`config::Bench`].)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
@@ -58,8 +128,16 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let config = &config::get().bench;
+    let bench_iterations = (config.target_time.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(
+            config.min_samples,
+            config.max_samples.max(config.min_samples),
+        );
+
+    for _ in 0..config.warmup {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -0,0 +1,680 @@
+/// The 3-bit computer of day 17.
+///
+/// A program is a list of 3-bit numbers that alternate between an opcode and its operand. The
+/// computer has the registers `a`, `b` and `c` and outputs 3-bit numbers. Besides running a
+/// program, this module can disassemble it, trace its execution and search for a value of `a`
+/// that makes the program output itself.
+use rayon::prelude::*;
+use std::fmt::{self, Display};
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opcode {
+    /// `a = a >> combo`
+    Adv,
+    /// `b = b ^ literal`
+    Bxl,
+    /// `b = combo & 7`
+    Bst,
+    /// jumps to `literal` if `a != 0`
+    Jnz,
+    /// `b = b ^ c`, the operand is ignored
+    Bxc,
+    /// outputs `combo & 7`
+    Out,
+    /// `b = a >> combo`
+    Bdv,
+    /// `c = a >> combo`
+    Cdv,
+}
+
+impl Opcode {
+    const ALL: [Opcode; 8] = [
+        Opcode::Adv,
+        Opcode::Bxl,
+        Opcode::Bst,
+        Opcode::Jnz,
+        Opcode::Bxc,
+        Opcode::Out,
+        Opcode::Bdv,
+        Opcode::Cdv,
+    ];
+
+    #[must_use]
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+        }
+    }
+
+    /// Whether the operand is a combo operand, i.e. `4`, `5` and `6` refer to a register.
+    #[must_use]
+    pub fn has_combo_operand(self) -> bool {
+        matches!(
+            self,
+            Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub operand: u8,
+}
+
+impl Instruction {
+    /// The operand as it is written in the disassembly, e.g. `a` for the combo operand `4`.
+    fn operand_str(self) -> String {
+        match (self.opcode.has_combo_operand(), self.operand) {
+            (true, 4) => "a".into(),
+            (true, 5) => "b".into(),
+            (true, 6) => "c".into(),
+            (true, 7) => "?".into(),
+            (_, operand) => operand.to_string(),
+        }
+    }
+
+    /// What the instruction does, written as pseudo code.
+    #[must_use]
+    pub fn pseudo_code(self) -> String {
+        let operand = self.operand_str();
+        match self.opcode {
+            Opcode::Adv => format!("a = a >> {operand}"),
+            Opcode::Bxl => format!("b = b ^ {operand}"),
+            Opcode::Bst => format!("b = {operand} & 7"),
+            Opcode::Jnz => format!("if a != 0: goto {operand}"),
+            Opcode::Bxc => "b = b ^ c".into(),
+            Opcode::Out => format!("out {operand} & 7"),
+            Opcode::Bdv => format!("b = a >> {operand}"),
+            Opcode::Cdv => format!("c = a >> {operand}"),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.opcode.mnemonic(), self.operand_str())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Parser(String),
+    /// The combo operand `7` is reserved and does not appear in valid programs.
+    ReservedOperand {
+        ip: usize,
+    },
+    /// The program does not have the shape that the quine search relies on.
+    UnsupportedProgram(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse program: {e}"),
+            Error::ReservedOperand { ip } => {
+                write!(
+                    f,
+                    "reserved combo operand 7 used by the instruction at {ip}"
+                )
+            }
+            Error::UnsupportedProgram(e) => write!(f, "unsupported program: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// What happened when the computer executed an instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Continue,
+    Output(u8),
+    /// The instruction pointer moved past the end of the program.
+    Halt,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Computer {
+    pub registers: [u64; 3],
+    pub ip: usize,
+    program: Vec<u8>,
+}
+
+impl Computer {
+    /// Creates a computer with the given registers. The program is not validated until it runs.
+    #[must_use]
+    pub fn new(registers: [u64; 3], program: Vec<u8>) -> Self {
+        Self {
+            registers,
+            ip: 0,
+            program,
+        }
+    }
+
+    /// Parses the puzzle input, i.e. the lines `Register A: 729` to `Register C: 0` followed by
+    /// `Program: 0,1,5,4,3,0`.
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut registers = [0; 3];
+        let mut program = None;
+
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| Error::Parser(format!("expected `name: value`, got `{line}`")))?;
+            let value = value.trim();
+
+            let register = match name.trim() {
+                "Register A" => 0,
+                "Register B" => 1,
+                "Register C" => 2,
+                "Program" => {
+                    let numbers = value
+                        .split(',')
+                        .map(|n| n.trim().parse::<u8>().ok().filter(|&n| n < 8))
+                        .collect::<Option<Vec<u8>>>()
+                        .ok_or_else(|| Error::Parser(format!("invalid program `{value}`")))?;
+                    program = Some(numbers);
+                    continue;
+                }
+                name => return Err(Error::Parser(format!("unknown field `{name}`"))),
+            };
+
+            registers[register] = value
+                .parse()
+                .map_err(|_| Error::Parser(format!("invalid register value `{value}`")))?;
+        }
+
+        let program = program.ok_or_else(|| Error::Parser("missing program".into()))?;
+        Ok(Self::new(registers, program))
+    }
+
+    #[must_use]
+    pub fn program(&self) -> &[u8] {
+        &self.program
+    }
+
+    /// The instruction at `ip`, if `ip` points into the program.
+    #[must_use]
+    pub fn instruction(&self, ip: usize) -> Option<Instruction> {
+        Some(Instruction {
+            opcode: *Opcode::ALL.get(usize::from(*self.program.get(ip)?))?,
+            // an opcode without an operand at the end of the program halts the computer.
+            operand: *self.program.get(ip + 1)?,
+        })
+    }
+
+    /// The instructions of the program with their address, mnemonic and pseudo code, one per line.
+    #[must_use]
+    pub fn disassemble(&self) -> String {
+        (0..self.program.len())
+            .step_by(2)
+            .filter_map(|ip| {
+                let instruction = self.instruction(ip)?;
+                Some(format!(
+                    "{ip:02}: {:<6} ; {}",
+                    instruction.to_string(),
+                    instruction.pseudo_code()
+                ))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn combo(&self, operand: u8) -> Result<u64, Error> {
+        match operand {
+            0..=3 => Ok(u64::from(operand)),
+            4..=6 => Ok(self.registers[usize::from(operand - 4)]),
+            _ => Err(Error::ReservedOperand { ip: self.ip }),
+        }
+    }
+
+    /// Executes the instruction at the instruction pointer.
+    pub fn step(&mut self) -> Result<Step, Error> {
+        let Some(Instruction { opcode, operand }) = self.instruction(self.ip) else {
+            return Ok(Step::Halt);
+        };
+
+        let [a, b, c] = self.registers;
+        // shifting by 64 or more bits clears the register.
+        let shift = |by: u64| u32::try_from(by).ok().and_then(|by| a.checked_shr(by));
+        let mut step = Step::Continue;
+
+        match opcode {
+            Opcode::Adv => self.registers[0] = shift(self.combo(operand)?).unwrap_or(0),
+            Opcode::Bxl => self.registers[1] = b ^ u64::from(operand),
+            Opcode::Bst => self.registers[1] = self.combo(operand)? & 7,
+            Opcode::Jnz if a != 0 => {
+                self.ip = usize::from(operand);
+                return Ok(step);
+            }
+            Opcode::Jnz => {}
+            Opcode::Bxc => self.registers[1] = b ^ c,
+            Opcode::Out => step = Step::Output((self.combo(operand)? & 7) as u8),
+            Opcode::Bdv => self.registers[1] = shift(self.combo(operand)?).unwrap_or(0),
+            Opcode::Cdv => self.registers[2] = shift(self.combo(operand)?).unwrap_or(0),
+        }
+
+        self.ip += 2;
+        Ok(step)
+    }
+
+    /// Runs until the next output and returns it, or `None` once the computer halts.
+    pub fn next_output(&mut self) -> Result<Option<u8>, Error> {
+        loop {
+            match self.step()? {
+                Step::Continue => {}
+                Step::Output(value) => return Ok(Some(value)),
+                Step::Halt => return Ok(None),
+            }
+        }
+    }
+
+    /// Runs until the computer halts and returns all outputs.
+    pub fn run(&mut self) -> Result<Vec<u8>, Error> {
+        let mut output = vec![];
+        while let Some(value) = self.next_output()? {
+            output.push(value);
+        }
+        Ok(output)
+    }
+
+    /// Runs until the computer halts like [`Computer::run`], and passes a line per executed
+    /// instruction to `log`. Each line shows the instruction and the registers after it.
+    pub fn trace(&mut self, mut log: impl FnMut(&str)) -> Result<Vec<u8>, Error> {
+        let mut output = vec![];
+
+        loop {
+            let ip = self.ip;
+            let Some(instruction) = self.instruction(ip) else {
+                return Ok(output);
+            };
+
+            let step = self.step()?;
+            let [a, b, c] = self.registers;
+            let mut line = format!(
+                "{ip:02}: {:<6} | a={a} b={b} c={c}",
+                instruction.to_string()
+            );
+
+            if let Step::Output(value) = step {
+                line.push_str(&format!(" | out {value}"));
+                output.push(value);
+            }
+
+            log(&line);
+        }
+    }
+
+    /// Finds the smallest value of register `a` for which the program outputs itself.
+    ///
+    /// This works for programs that loop until `a` is zero and shift `a` right by 3 bits once
+    /// per loop, i.e. contain a single `adv 3` and end with `jnz 0`. Every output then only
+    /// depends on the remaining bits of `a`, so the program can be matched from its last output
+    /// to its first by adding 3 bits of `a` at a time.
+    pub fn find_quine(&self) -> Result<Option<u64>, Error> {
+        self.check_quine_search()?;
+        self.find_quine_from(&self.compile(), 0, self.program.len())
+    }
+
+    /// Checks that [`Computer::find_quine`] supports the program, without searching.
+    pub fn check_quine_search(&self) -> Result<(), Error> {
+        let instructions: Vec<Instruction> = (0..self.program.len())
+            .step_by(2)
+            .filter_map(|ip| self.instruction(ip))
+            .collect();
+
+        let shifts_a = |i: &&Instruction| i.opcode == Opcode::Adv;
+        match instructions.iter().filter(shifts_a).collect::<Vec<_>>()[..] {
+            [Instruction { operand: 3, .. }] => {}
+            _ => {
+                return Err(Error::UnsupportedProgram(
+                    "expected a single `adv 3` per loop".into(),
+                ))
+            }
+        }
+
+        if instructions.last().map(ToString::to_string).as_deref() != Some("jnz 0") {
+            return Err(Error::UnsupportedProgram(
+                "expected to end with `jnz 0`".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Tries to extend `a` by 3 bits so the program outputs its instructions from `matched - 1`.
+    fn find_quine_from(
+        &self,
+        compiled: &Compiled,
+        a: u64,
+        matched: usize,
+    ) -> Result<Option<u64>, Error> {
+        if matched == 0 {
+            return Ok(Some(a));
+        }
+
+        for bits in 0..8 {
+            let Some(next) = a.checked_mul(8).map(|a| a | bits) else {
+                return Ok(None);
+            };
+
+            let registers = [next, self.registers[1], self.registers[2]];
+            if compiled.outputs(registers, &self.program[matched - 1..])? {
+                if let Some(a) = self.find_quine_from(compiled, next, matched - 1)? {
+                    return Ok(Some(a));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// What a compiled instruction tells the run loop to do next.
+enum Flow {
+    Next,
+    Jump(usize),
+    Output(u8),
+    Reserved,
+    Halt,
+}
+
+type Op = Box<dyn Fn(&mut [u64; 3]) -> Flow + Send + Sync>;
+
+/// A program that was compiled into one closure per address, see [`Computer::compile`].
+///
+/// Operands are decoded once while compiling, so running the program only calls the closures
+/// and follows jumps. This makes it a lot faster than [`Computer::run`] when a program is run
+/// with many different registers, e.g. when searching for a value of `a`.
+pub struct Compiled {
+    ops: Vec<Op>,
+}
+
+impl Computer {
+    /// Compiles the program. Reserved operands are only reported when they are executed and
+    /// invalid opcodes halt the program, like they do in the interpreter.
+    #[must_use]
+    pub fn compile(&self) -> Compiled {
+        // jumps can target odd addresses, so every address gets its own closure.
+        let ops = (0..self.program.len().saturating_sub(1))
+            .map(|ip| {
+                let Some(Instruction { opcode, operand }) = self.instruction(ip) else {
+                    return Box::new(|_: &mut [u64; 3]| Flow::Halt) as Op;
+                };
+                let literal = u64::from(operand);
+
+                match opcode {
+                    Opcode::Adv => combo_op(operand, |r, v| {
+                        r[0] = shift(r[0], v);
+                        Flow::Next
+                    }),
+                    Opcode::Bxl => Box::new(move |r: &mut [u64; 3]| {
+                        r[1] ^= literal;
+                        Flow::Next
+                    }),
+                    Opcode::Bst => combo_op(operand, |r, v| {
+                        r[1] = v & 7;
+                        Flow::Next
+                    }),
+                    Opcode::Jnz => Box::new(move |r: &mut [u64; 3]| match r[0] {
+                        0 => Flow::Next,
+                        _ => Flow::Jump(usize::from(operand)),
+                    }),
+                    Opcode::Bxc => Box::new(|r: &mut [u64; 3]| {
+                        r[1] ^= r[2];
+                        Flow::Next
+                    }),
+                    Opcode::Out => combo_op(operand, |_, v| Flow::Output((v & 7) as u8)),
+                    Opcode::Bdv => combo_op(operand, |r, v| {
+                        r[1] = shift(r[0], v);
+                        Flow::Next
+                    }),
+                    Opcode::Cdv => combo_op(operand, |r, v| {
+                        r[2] = shift(r[0], v);
+                        Flow::Next
+                    }),
+                }
+            })
+            .collect();
+
+        Compiled { ops }
+    }
+}
+
+/// Compiles an instruction with a combo operand, which is resolved here instead of on every run.
+fn combo_op(operand: u8, op: fn(&mut [u64; 3], u64) -> Flow) -> Op {
+    match operand {
+        0..=3 => {
+            let literal = u64::from(operand);
+            Box::new(move |r| op(r, literal))
+        }
+        4..=6 => {
+            let register = usize::from(operand - 4);
+            Box::new(move |r| {
+                let value = r[register];
+                op(r, value)
+            })
+        }
+        _ => Box::new(|_| Flow::Reserved),
+    }
+}
+
+/// `a >> by`, where shifting by 64 or more bits results in zero.
+fn shift(a: u64, by: u64) -> u64 {
+    u32::try_from(by)
+        .ok()
+        .and_then(|by| a.checked_shr(by))
+        .unwrap_or(0)
+}
+
+impl Compiled {
+    /// Runs the program with the given registers until it halts, passing every output to
+    /// `on_output`. Stops early once `on_output` returns `false`.
+    fn execute(
+        &self,
+        mut registers: [u64; 3],
+        mut on_output: impl FnMut(u8) -> bool,
+    ) -> Result<(), Error> {
+        let mut ip = 0;
+
+        while let Some(op) = self.ops.get(ip) {
+            match op(&mut registers) {
+                Flow::Next => ip += 2,
+                Flow::Jump(target) => ip = target,
+                Flow::Output(value) => {
+                    if !on_output(value) {
+                        break;
+                    }
+                    ip += 2;
+                }
+                Flow::Reserved => return Err(Error::ReservedOperand { ip }),
+                Flow::Halt => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the program until it halts and returns all outputs, like [`Computer::run`].
+    pub fn run(&self, registers: [u64; 3]) -> Result<Vec<u8>, Error> {
+        let mut output = vec![];
+        self.execute(registers, |value| {
+            output.push(value);
+            true
+        })?;
+        Ok(output)
+    }
+
+    /// Whether the program outputs exactly `expected`. Stops at the first wrong output.
+    pub fn outputs(&self, registers: [u64; 3], expected: &[u8]) -> Result<bool, Error> {
+        let mut matched = 0;
+        let mut matches = true;
+
+        self.execute(registers, |value| {
+            matches = expected.get(matched) == Some(&value);
+            matched += 1;
+            matches
+        })?;
+
+        Ok(matches && matched == expected.len())
+    }
+
+    /// Searches `range` in parallel for the smallest value of register `a` for which the program
+    /// outputs `expected`, with `b` and `c` taken from `registers`. Runs that hit a reserved
+    /// operand do not match.
+    #[must_use]
+    pub fn search(&self, range: Range<u64>, registers: [u64; 3], expected: &[u8]) -> Option<u64> {
+        let [_, b, c] = registers;
+        search(range, |a| self.outputs([a, b, c], expected) == Ok(true))
+    }
+}
+
+/// Finds the smallest value in `range` that satisfies `predicate`, checking values in parallel.
+pub fn search(range: Range<u64>, predicate: impl Fn(u64) -> bool + Sync) -> Option<u64> {
+    range.into_par_iter().find_first(|&a| predicate(a))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{search, Computer, Error};
+    use std::time::Instant;
+
+    const EXAMPLE: &str = "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0";
+
+    #[test]
+    fn runs_programs() {
+        let mut computer = Computer::parse(EXAMPLE).unwrap();
+        assert_eq!(computer.run(), Ok(vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]));
+
+        let mut computer = Computer::new([0, 2024, 43690], vec![4, 0]);
+        assert_eq!(computer.run(), Ok(vec![]));
+        assert_eq!(computer.registers[1], 44354);
+    }
+
+    #[test]
+    fn disassembles_programs() {
+        let computer = Computer::new([0; 3], vec![2, 4, 1, 5, 7, 5, 4, 3, 0, 3, 5, 5, 3, 0]);
+        assert_eq!(
+            computer.disassemble(),
+            "00: bst a  ; b = a & 7\n\
+            02: bxl 5  ; b = b ^ 5\n\
+            04: cdv b  ; c = a >> b\n\
+            06: bxc 3  ; b = b ^ c\n\
+            08: adv 3  ; a = a >> 3\n\
+            10: out b  ; out b & 7\n\
+            12: jnz 0  ; if a != 0: goto 0"
+        );
+    }
+
+    #[test]
+    fn traces_programs() {
+        let mut lines = vec![];
+        let output = Computer::new([10, 0, 0], vec![5, 0, 5, 1, 5, 4])
+            .trace(|line| lines.push(line.to_string()))
+            .unwrap();
+
+        assert_eq!(output, [0, 1, 2]);
+        assert_eq!(lines[2], "04: out a  | a=10 b=0 c=0 | out 2");
+    }
+
+    #[test]
+    fn reports_reserved_operands() {
+        let mut computer = Computer::new([1, 0, 0], vec![1, 7, 2, 7]);
+        assert_eq!(computer.run(), Err(Error::ReservedOperand { ip: 2 }));
+        assert!(Computer::parse("Program: 0,8").is_err());
+    }
+
+    #[test]
+    fn finds_quines() {
+        let computer = Computer::new([2024, 0, 0], vec![0, 3, 5, 4, 3, 0]);
+        assert_eq!(computer.find_quine(), Ok(Some(117440)));
+
+        let program = vec![2, 4, 1, 1, 7, 5, 1, 5, 4, 0, 5, 5, 0, 3, 3, 0];
+        let computer = Computer::new([0; 3], program);
+        let a = computer.find_quine().unwrap().unwrap();
+        let mut quine = Computer::new([a, 0, 0], computer.program().to_vec());
+        assert_eq!(quine.run().unwrap(), computer.program());
+
+        let computer = Computer::new([0; 3], vec![0, 1, 5, 4, 3, 0]);
+        assert!(matches!(
+            computer.find_quine(),
+            Err(Error::UnsupportedProgram(_))
+        ));
+    }
+
+    #[test]
+    fn compiled_agrees_with_interpreter() {
+        let programs = [
+            vec![0, 1, 5, 4, 3, 0],
+            vec![2, 4, 1, 1, 7, 5, 1, 5, 4, 0, 5, 5, 0, 3, 3, 0],
+            vec![1, 7, 2, 7],
+            // jumps to an odd address.
+            vec![3, 3, 5, 4, 0, 1, 3, 0],
+            // halts at an invalid opcode.
+            vec![5, 4, 8, 0, 5, 5],
+        ];
+
+        for program in programs {
+            let compiled = Computer::new([0; 3], program.clone()).compile();
+            for a in 0..200 {
+                let registers = [a, 3, 5];
+                let expected = Computer::new(registers, program.clone()).run();
+                assert_eq!(
+                    compiled.run(registers),
+                    expected,
+                    "{program:?} with a = {a}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn searches_registers() {
+        let computer = Computer::new([0; 3], vec![0, 3, 5, 4, 3, 0]);
+        let compiled = computer.compile();
+
+        assert_eq!(
+            compiled.search(0..200_000, [0; 3], computer.program()),
+            Some(117440)
+        );
+        assert_eq!(compiled.search(0..1000, [0; 3], computer.program()), None);
+        assert_eq!(compiled.outputs([117440, 0, 0], &[0, 3]), Ok(false));
+        assert_eq!(search(10..100, |a| a % 7 == 0), Some(14));
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with `cargo test --release --features test_lib -- --ignored`"]
+    fn bench_compiled_against_interpreter() {
+        let program = vec![2, 4, 1, 1, 7, 5, 1, 5, 4, 0, 5, 5, 0, 3, 3, 0];
+        let range = 0..(1 << 24);
+
+        let timer = Instant::now();
+        let interpreted = search(range.clone(), |a| {
+            Computer::new([a, 0, 0], program.clone()).run().ok() == Some(program[8..].to_vec())
+        });
+        let interpreter = timer.elapsed();
+
+        let timer = Instant::now();
+        let compiled =
+            Computer::new([0; 3], program.clone())
+                .compile()
+                .search(range, [0; 3], &program[8..]);
+        let closures = timer.elapsed();
+
+        println!("interpreter: {interpreter:.1?}, compiled: {closures:.1?}");
+        assert_eq!(interpreted, compiled);
+    }
+}